@@ -0,0 +1,15 @@
+#!/bin/sh
+# Regenerates `release-keys.asc` from the Node.js release team's keys.
+# https://github.com/nodejs/release-keys
+set -eu
+
+RELEASE_KEYS_URL="https://raw.githubusercontent.com/nodejs/release-keys/main"
+GNUPGHOME="$(mktemp -d)"
+export GNUPGHOME
+trap 'rm -rf "$GNUPGHOME"' EXIT
+
+curl -fsSL "$RELEASE_KEYS_URL/keys.list" | while read -r fingerprint; do
+  curl -fsSL "$RELEASE_KEYS_URL/keys/$fingerprint.asc" | gpg --quiet --import
+done
+
+gpg --armor --export >"$(dirname "$0")/release-keys.asc"
@@ -0,0 +1,133 @@
+use clap::Args;
+
+use crate::{
+    types::{
+        node::{Channel, Release},
+        platforms::Platform,
+    },
+    utils::{alias, spinner},
+};
+
+use super::{NueCommand, install::VersionInputs};
+
+const RESERVED_NAMES: [&str; 7] = [
+    "all", "current", "latest", "lts", "node", "stable", "system",
+];
+
+#[derive(Args, Debug)]
+pub struct CommandArguments {
+    /// Name of the alias. Lists all aliases when omitted.
+    name: Option<String>,
+
+    /// Version, range, LTS code name or channel the alias points to. Shows
+    /// the alias when omitted.
+    target: Option<String>,
+
+    /// Remove the alias.
+    #[arg(long, requires = "name", conflicts_with = "target")]
+    remove: bool,
+}
+
+impl NueCommand for CommandArguments {
+    fn run(&self) -> anyhow::Result<()> {
+        let Some(name) = self.name.as_deref().map(str::to_lowercase) else {
+            let aliases = alias::get_all_aliases()?;
+            if aliases.is_empty() {
+                println!("No alias found. Use `nue alias <name> <version>` to create one.");
+            }
+
+            for (name, target) in aliases {
+                println!("{name} -> {target}");
+            }
+
+            return Ok(());
+        };
+
+        if self.remove {
+            if !alias::remove_alias(&name)? {
+                anyhow::bail!("No alias named `{name}` found.");
+            }
+
+            println!("Removed alias `{name}`.");
+            return Ok(());
+        }
+
+        let Some(target) = &self.target else {
+            let target = alias::read_alias(&name)?
+                .ok_or_else(|| anyhow::anyhow!("No alias named `{name}` found."))?;
+            println!("{name} -> {target}");
+
+            return Ok(());
+        };
+
+        validate_name(&name)?;
+
+        let target = target.trim().to_lowercase();
+        if alias::read_alias(&target)?.is_some() {
+            anyhow::bail!("Aliases can't point to other aliases.");
+        }
+
+        let target_input = VersionInputs::parse_builtin(&target)?;
+        let code_names = lts_code_names()?;
+        if code_names.contains(&name) {
+            anyhow::bail!("`{name}` is an LTS code name and can't be used as an alias.");
+        }
+        if let VersionInputs::Lts(Some(code_name)) = &target_input {
+            if !code_names.contains(code_name) {
+                anyhow::bail!("`{target}` isn't a known version, range, LTS code name or channel.");
+            }
+        }
+
+        alias::write_alias(&name, &target)?;
+        println!("{name} -> {target}");
+
+        Ok(())
+    }
+}
+
+fn validate_name(name: &str) -> anyhow::Result<()> {
+    if !alias::is_valid_name(name) {
+        anyhow::bail!(
+            "`{name}` isn't a valid alias name, only letters, digits, `-` and `_` are allowed."
+        );
+    }
+
+    if RESERVED_NAMES.contains(&name) || Channel::split_version_input(name).is_some() {
+        anyhow::bail!("`{name}` is reserved and can't be used as an alias.");
+    }
+
+    if !matches!(
+        VersionInputs::parse_builtin(name)?,
+        VersionInputs::Lts(Some(_))
+    ) {
+        anyhow::bail!("`{name}` is a version or range and can't be used as an alias.");
+    }
+
+    Ok(())
+}
+
+/// Lowercased LTS code names from the release index, which falls back to the
+/// cached one when offline. Without any index, installed releases are used.
+fn lts_code_names() -> anyhow::Result<Vec<String>> {
+    let releases = spinner::run("Fetching releases...", |_| {
+        Release::get_all_releases(Channel::Release, Platform::require_current()?)
+    });
+
+    match releases {
+        Ok(releases) => Ok(releases
+            .iter()
+            .filter_map(|release| release.lts.code_name().map(str::to_lowercase))
+            .collect()),
+        Err(error) => {
+            let code_names = alias::local_lts_code_names()?;
+            if code_names.is_empty() {
+                return Err(error);
+            }
+
+            eprintln!(
+                "Failed to fetch releases ({error}), checking LTS code names of installed releases only."
+            );
+            Ok(code_names)
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::fs;
 
 use clap::Args;
-use demand::Spinner;
 
-use crate::{globals::NUE_PATH, utils::cache};
+use crate::{
+    globals::NUE_PATH,
+    utils::{cache, spinner},
+};
 
 use super::NueCommand;
 
@@ -19,18 +21,32 @@ impl NueCommand for CommandArguments {
             cached_downloads.retain(|download| used != download);
         }
 
-        if cached_downloads.is_empty() {
+        let partial_downloads = cache::find_partial_downloads()?;
+        let staging_directories = cache::find_staging_directories()?;
+
+        if cached_downloads.is_empty()
+            && partial_downloads.is_empty()
+            && staging_directories.is_empty()
+        {
             println!("Nothing to clean.");
             return Ok(());
         }
 
-        Spinner::new("Cleaning up...").run(|_| -> anyhow::Result<()> {
+        spinner::run("Cleaning up...", |_| -> anyhow::Result<()> {
             for download in cached_downloads {
                 fs::remove_dir_all(download)?;
             }
 
+            for download in partial_downloads {
+                fs::remove_file(download)?;
+            }
+
+            for staging_directory in staging_directories {
+                fs::remove_dir_all(staging_directory)?;
+            }
+
             Ok(())
-        })??;
+        })?;
 
         println!("Cleaned up unused Node downloads.");
 
@@ -0,0 +1,120 @@
+use core::sync::atomic::Ordering;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::{
+    exts::HyperlinkExt as _,
+    globals::QUIET,
+    types::{self, node::InstalledRelease, platforms::Platform},
+    utils::spinner,
+};
+
+use super::{NueCommand, install::VersionInputs};
+
+#[derive(Args, Debug)]
+pub struct CommandArguments {
+    /// Version of Node to show. Defaults to the version in `.node-version`,
+    /// `.nvmrc` or `package.json`, the `default` alias, or the latest release.
+    version: Option<VersionInputs>,
+
+    /// Print the release as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct InfoOutput<'a> {
+    version: String,
+    lts: Option<&'a str>,
+    date: chrono::NaiveDate,
+    npm: Option<String>,
+    v8: Option<&'a str>,
+    uv: Option<&'a str>,
+    zlib: Option<&'a str>,
+    openssl: Option<&'a str>,
+    modules: Option<&'a str>,
+    security: bool,
+    files: &'a [String],
+    supported: bool,
+    installed: bool,
+}
+
+impl NueCommand for CommandArguments {
+    fn run(&self) -> anyhow::Result<()> {
+        if self.json {
+            QUIET.store(true, Ordering::Relaxed);
+        }
+
+        let version = VersionInputs::resolve(self.version.as_ref())?;
+
+        let releases = spinner::run("Fetching releases...", |_| {
+            types::node::Release::get_all_releases(version.channel(), Platform::require_current()?)
+        })?;
+        let release = version.select_release(&releases).ok_or_else(|| {
+            anyhow::anyhow!("No release found with given version or LTS code name.")
+        })?;
+
+        let installed = InstalledRelease::get_all_installed()?
+            .iter()
+            .any(|installed| {
+                installed.version == release.version && installed.channel == release.channel
+            });
+
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&InfoOutput {
+                    version: release.version.to_string(),
+                    lts: release.lts.code_name(),
+                    date: release.date,
+                    npm: release.npm.as_ref().map(ToString::to_string),
+                    v8: release.v8.as_deref(),
+                    uv: release.uv.as_deref(),
+                    zlib: release.zlib.as_deref(),
+                    openssl: release.openssl.as_deref(),
+                    modules: release.modules.as_deref(),
+                    security: release.security,
+                    files: &release.files,
+                    supported: release.is_supported_by_current_platform(),
+                    installed,
+                })?
+            );
+            return Ok(());
+        }
+
+        println!(
+            "Node v{}",
+            release.version.hyperlink(release.get_github_release_url())
+        );
+        println!("  LTS:       {}", release.lts.code_name().unwrap_or("no"));
+        println!("  Released:  {}", release.date);
+        println!(
+            "  npm:       {}",
+            release
+                .npm
+                .as_ref()
+                .map_or_else(|| "-".to_string(), ToString::to_string)
+        );
+        println!("  V8:        {}", release.v8.as_deref().unwrap_or("-"));
+        println!("  libuv:     {}", release.uv.as_deref().unwrap_or("-"));
+        println!("  zlib:      {}", release.zlib.as_deref().unwrap_or("-"));
+        println!("  OpenSSL:   {}", release.openssl.as_deref().unwrap_or("-"));
+        println!("  Modules:   {}", release.modules.as_deref().unwrap_or("-"));
+        println!(
+            "  Security:  {}",
+            if release.security { "yes" } else { "no" }
+        );
+        println!(
+            "  Supported: {}",
+            if release.is_supported_by_current_platform() {
+                "yes"
+            } else {
+                "no"
+            }
+        );
+        println!("  Installed: {}", if installed { "yes" } else { "no" });
+
+        Ok(())
+    }
+}
@@ -1,87 +1,191 @@
-use std::{ffi, fmt, str};
+use core::sync::atomic::Ordering;
+
+use std::{ffi, fmt, path, str};
 
 use clap::Args;
-use demand::Spinner;
+use serde::Serialize;
 
 use crate::{
-    types,
-    utils::{self, cache},
+    globals::{NUE_RELEASES_PATH, OFFLINE, QUIET},
+    types::{self, platforms::Platform},
+    utils::{self, alias, cache, index, project, spinner},
 };
 
-use super::NueCommand;
+use super::{NueCommand, outdated};
 
 #[derive(Debug, Default, Clone)]
 pub enum VersionInputs {
-    VersionString(String),
+    VersionString(node_semver::Range),
     #[default]
     Latest,
     Lts(Option<String>),
+    Channel(types::node::Channel, Option<node_semver::Range>),
 }
 
 #[derive(Args, Debug)]
 pub struct CommandArguments {
-    /// Optional version of Node to install.
-    #[arg(default_value_t = VersionInputs::default())]
-    pub version: VersionInputs,
+    /// Optional version of Node to install. Defaults to the version in
+    /// `.node-version`, `.nvmrc` or `package.json`, the `default` alias, or the
+    /// latest release.
+    pub version: Option<VersionInputs>,
+
+    /// Install from a local release archive or directory instead of downloading,
+    /// e.g. `node-v20.11.1-linux-x64.tar.xz`.
+    #[arg(long, conflicts_with = "version")]
+    pub from_file: Option<path::PathBuf>,
+
+    /// Install for another OS (`linux`, `darwin` or `win`). Releases that can't
+    /// run here are only unpacked, not linked.
+    #[arg(long, conflicts_with = "from_file")]
+    pub platform: Option<String>,
+
+    /// Install for another architecture, e.g. `x64` to run under Rosetta.
+    #[arg(long, conflicts_with = "from_file")]
+    pub arch: Option<String>,
 
     /// Force install.
     #[arg(long)]
     pub force: bool,
+
+    /// Skip verifying the signature of release checksums.
+    #[arg(long)]
+    pub skip_signature: bool,
+
+    /// Print the result as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+enum InstallStatus {
+    AlreadyInstalled,
+    Cached,
+    Downloaded,
+    Local,
+}
+
+#[derive(Serialize, Debug)]
+struct InstallOutput {
+    version: String,
+    platform: String,
+    path: path::PathBuf,
+    status: InstallStatus,
 }
 
 impl NueCommand for CommandArguments {
     fn run(&self) -> anyhow::Result<()> {
+        if self.json {
+            QUIET.store(true, Ordering::Relaxed);
+        }
+
+        let platform = Platform::from_node_names(self.platform.as_deref(), self.arch.as_deref())?;
+
+        if let Some(from_file) = &self.from_file {
+            let installed_release = spinner::run(
+                format!("Installing from `{}`...", from_file.display()),
+                |_| types::node::InstalledRelease::install_from_file(from_file),
+            )?;
+
+            return self.report(
+                &installed_release.version,
+                installed_release.platform,
+                installed_release.path,
+                InstallStatus::Local,
+            );
+        }
+
+        let version = VersionInputs::resolve(self.version.as_ref())?;
+
+        if OFFLINE.load(Ordering::Relaxed)
+            && !index::is_index_cached(version.channel(), platform)
+            && platform.is_runnable()
+        {
+            let installed_release = types::node::InstalledRelease::get_all_installed()?
+                .into_iter()
+                .find(|release| release.platform == platform && version.matches_installed(release))
+                .ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "No installed release found with given version or LTS code name, and releases can't be fetched in offline mode."
+                    )
+                })?;
+
+            let status = if installed_release.is_active() && !self.force {
+                InstallStatus::AlreadyInstalled
+            } else {
+                installed_release.link()?;
+                InstallStatus::Cached
+            };
+
+            return self.report(
+                &installed_release.version,
+                platform,
+                installed_release.path,
+                status,
+            );
+        }
+
         let mut selected_release: Option<types::node::Release> = None;
-        Spinner::new("Fetching releases...").run(|spinner| -> anyhow::Result<()> {
-            let releases = types::node::Release::get_all_releases()?;
+        let mut security_update: Option<types::node::Release> = None;
+        spinner::run("Fetching releases...", |spinner| -> anyhow::Result<()> {
+            let releases: Vec<_> =
+                types::node::Release::get_all_releases(version.channel(), platform)?
+                    .into_iter()
+                    .filter(|release| release.is_supported_by(platform))
+                    .collect();
 
             spinner.title("Filtering releases...")?;
-            selected_release = match &self.version {
-                VersionInputs::VersionString(version) => releases
-                    .iter()
-                    .find(|release| format!("{}", release.version).starts_with(version)),
-                VersionInputs::Lts(Some(code_name)) => releases.iter().find(|release| {
-                    matches!(
-                        &release.lts,
-                        types::node::Lts::CodeName(name) if &name.to_lowercase() == code_name
-                    )
-                }),
-                VersionInputs::Lts(None) => {
-                    releases.iter().find(|release| release.lts.is_code_name())
-                }
-                VersionInputs::Latest => releases.iter().max_by_key(|release| &release.version),
-            }
-            .cloned();
+            selected_release = version.select_release(&releases).cloned();
+            security_update = selected_release
+                .as_ref()
+                .and_then(|release| {
+                    types::node::Release::find_security_update(&releases, &release.version)
+                })
+                .cloned();
 
             Ok(())
-        })??;
+        })?;
 
         match selected_release {
             Some(release) => {
-                if release.check_installed()? && !self.force {
-                    println!(
-                        "Node v{} is already installed. Use `--force` to re-install.",
-                        release.version
-                    );
-                    return Ok(());
-                }
-
-                let cached_downloads = cache::find_cached_node_downloads()?;
-                if cached_downloads
-                    .iter()
-                    .map(|path| path.file_name())
-                    .any(|file| file == Some(ffi::OsStr::new(&release.get_archive_string())))
-                {
-                    release.install_from_cache(&cached_downloads)?;
+                let status = if release.check_installed(platform)? && !self.force {
+                    InstallStatus::AlreadyInstalled
                 } else {
-                    release.install()?;
-                }
+                    let cached_downloads = cache::find_cached_node_downloads()?;
+                    if platform.is_runnable()
+                        && cached_downloads
+                            .iter()
+                            .map(|path| path.file_name())
+                            .any(|file| {
+                                file == Some(ffi::OsStr::new(
+                                    &release.get_release_directory_name(platform),
+                                ))
+                            })
+                    {
+                        release.install_from_cache(platform, &cached_downloads)?;
+                        InstallStatus::Cached
+                    } else if OFFLINE.load(Ordering::Relaxed) {
+                        anyhow::bail!(
+                            "Node v{0} isn't downloaded yet and can't be installed in offline mode. Run `nue install {0}` without `--offline` first.",
+                            release.version
+                        );
+                    } else {
+                        release.install(platform, self.skip_signature)?;
+                        InstallStatus::Downloaded
+                    }
+                };
 
-                println!("Node v{} is now installed!", release.version);
+                self.report(
+                    &release.version,
+                    platform,
+                    NUE_RELEASES_PATH.join(release.get_release_directory_name(platform)),
+                    status,
+                )?;
 
-                if !utils::check::is_node_in_path() {
-                    println!(
-                        "Node is installed, but its path isn't in `PATH`. Run `nue env` to fix it."
+                if let Some(security_update) = &security_update {
+                    eprintln!(
+                        "{}",
+                        outdated::security_warning(&release.version, security_update)
                     );
                 }
             }
@@ -94,13 +198,187 @@ impl NueCommand for CommandArguments {
     }
 }
 
+impl CommandArguments {
+    fn report(
+        &self,
+        version: &node_semver::Version,
+        platform: Platform,
+        path: path::PathBuf,
+        status: InstallStatus,
+    ) -> anyhow::Result<()> {
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&InstallOutput {
+                    version: version.to_string(),
+                    platform: platform.node_platform_string(),
+                    path,
+                    status,
+                })?
+            );
+            return Ok(());
+        }
+
+        if matches!(status, InstallStatus::AlreadyInstalled) {
+            println!("Node v{version} is already installed. Use `--force` to re-install.");
+            return Ok(());
+        }
+
+        if !platform.is_runnable() {
+            println!(
+                "Node v{version} for `{}` is unpacked at `{}`.",
+                platform.node_platform_string(),
+                path.display()
+            );
+            return Ok(());
+        }
+
+        println!("Node v{version} is now installed!");
+
+        if !utils::check::is_node_in_path() {
+            println!("Node is installed, but its path isn't in `PATH`. Run `nue env` to fix it.");
+        }
+
+        Ok(())
+    }
+}
+
+impl VersionInputs {
+    /// Input selecting exactly `release`, within its channel.
+    pub fn exact(release: &types::node::Release) -> anyhow::Result<Self> {
+        let range = format!("={}", release.version).parse()?;
+
+        Ok(match release.channel {
+            types::node::Channel::Release => Self::VersionString(range),
+            channel => Self::Channel(channel, Some(range)),
+        })
+    }
+
+    pub fn select_release<'a>(
+        &self,
+        releases: &'a [types::node::Release],
+    ) -> Option<&'a types::node::Release> {
+        match self {
+            Self::VersionString(range) => releases
+                .iter()
+                .filter(|release| range.satisfies(&release.version))
+                .max_by_key(|release| &release.version),
+            Self::Lts(Some(code_name)) => releases.iter().find(|release| {
+                matches!(
+                    &release.lts,
+                    types::node::Lts::CodeName(name) if &name.to_lowercase() == code_name
+                )
+            }),
+            Self::Lts(None) => releases.iter().find(|release| release.lts.is_code_name()),
+            Self::Latest | Self::Channel(_, None) => {
+                releases.iter().max_by_key(|release| &release.version)
+            }
+            Self::Channel(_, Some(range)) => releases
+                .iter()
+                .filter(|release| types::node::Channel::satisfies(range, &release.version))
+                .max_by_key(|release| &release.version),
+        }
+    }
+
+    pub fn matches_installed(&self, release: &types::node::InstalledRelease) -> bool {
+        if release.channel != self.channel() {
+            return false;
+        }
+
+        match self {
+            Self::VersionString(range) => range.satisfies(&release.version),
+            Self::Latest | Self::Channel(_, None) => true,
+            Self::Lts(Some(code_name)) => matches!(
+                release.lts(),
+                types::node::Lts::CodeName(name) if name.to_lowercase() == *code_name
+            ),
+            Self::Lts(None) => release.lts().is_code_name(),
+            Self::Channel(_, Some(range)) => {
+                types::node::Channel::satisfies(range, &release.version)
+            }
+        }
+    }
+
+    pub const fn channel(&self) -> types::node::Channel {
+        match self {
+            Self::Channel(channel, _) => *channel,
+            _ => types::node::Channel::Release,
+        }
+    }
+
+    pub fn resolve(version: Option<&Self>) -> anyhow::Result<Self> {
+        match version {
+            Some(version) => version.clone().resolve_alias(),
+            None => match project::find_node_version()? {
+                Some((version, path)) => {
+                    if !QUIET.load(Ordering::Relaxed) {
+                        println!("Using Node `{version}` from `{}`.", path.display());
+                    }
+
+                    Self::parse_builtin(&version)?.resolve_alias()
+                }
+                None => match alias::read_alias("default")? {
+                    Some(target) => Self::parse_builtin(&target),
+                    None => Ok(Self::default()),
+                },
+            },
+        }
+    }
+
+    /// Aliases are only looked up for inputs that would otherwise be taken as
+    /// an LTS code name. Alias names can't be LTS code names when created, but
+    /// a later LTS line can take the name, in which case the LTS line wins.
+    pub fn resolve_alias(self) -> anyhow::Result<Self> {
+        let Self::Lts(Some(name)) = &self else {
+            return Ok(self);
+        };
+        let Some(target) = alias::read_alias(name)? else {
+            return Ok(self);
+        };
+
+        if alias::local_lts_code_names()?.contains(name) {
+            eprintln!(
+                "`{name}` is both an alias and an LTS code name, using the LTS line. Run `nue alias --remove {name}` to remove the alias."
+            );
+            return Ok(self);
+        }
+
+        Self::parse_builtin(&target)
+    }
+
+    /// Parses an input without resolving aliases, which also keeps aliases
+    /// from pointing to other aliases.
+    pub fn parse_builtin(s: &str) -> anyhow::Result<Self> {
+        let s = s.trim().to_lowercase();
+
+        if let Some((channel, version)) = types::node::Channel::split_version_input(&s) {
+            let range = version
+                .map(|version| version.strip_prefix('v').unwrap_or(version).parse())
+                .transpose()?;
+            return Ok(Self::Channel(channel, range));
+        }
+
+        match s.as_str() {
+            "latest" => Ok(Self::Latest),
+            "lts" => Ok(Self::Lts(None)),
+            _ => Ok(s
+                .strip_prefix('v')
+                .unwrap_or(&s)
+                .parse::<node_semver::Range>()
+                .map_or_else(|_| Self::Lts(Some(s.clone())), Self::VersionString)),
+        }
+    }
+}
+
 impl fmt::Display for VersionInputs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::VersionString(version) => write!(f, "{version}"),
+            Self::VersionString(range) => write!(f, "{range}"),
             Self::Latest => write!(f, "latest"),
             Self::Lts(Some(code_name)) => write!(f, "{code_name}"),
             Self::Lts(None) => write!(f, "lts"),
+            Self::Channel(channel, Some(range)) => write!(f, "{channel}/{range}"),
+            Self::Channel(channel, None) => write!(f, "{channel}"),
         }
     }
 }
@@ -108,17 +386,88 @@ impl fmt::Display for VersionInputs {
 impl str::FromStr for VersionInputs {
     type Err = anyhow::Error;
 
+    /// Clap parses arguments before the config is loaded, so aliases are only
+    /// resolved later by `resolve`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.to_lowercase();
+        Self::parse_builtin(s)
+    }
+}
 
-        match s.as_str() {
-            "latest" => Ok(Self::Latest),
-            "lts" => Ok(Self::Lts(None)),
-            _ if s.starts_with('v') && s[1..].parse::<node_semver::Range>().is_ok() => {
-                Ok(Self::VersionString(s[1..].to_string()))
-            }
-            _ if s.parse::<node_semver::Range>().is_ok() => Ok(Self::VersionString(s)),
-            _ => Ok(Self::Lts(Some(s))),
-        }
+#[cfg(test)]
+mod tests {
+    use clap::Parser as _;
+
+    use crate::{
+        cli::{Cli, Subcommands},
+        globals::LIBC,
+        types::node::Release,
+    };
+
+    use super::VersionInputs;
+
+    fn releases(versions: &[(&str, Option<&str>)]) -> Vec<Release> {
+        versions
+            .iter()
+            .map(|(version, lts)| {
+                serde_json::from_value(serde_json::json!({
+                    "version": format!("v{version}"),
+                    "files": [],
+                    "lts": lts.map_or(serde_json::Value::Bool(false), Into::into),
+                    "date": "2024-01-01",
+                    "security": false,
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn select(input: &str) -> Option<String> {
+        let releases = releases(&[
+            ("22.0.0", None),
+            ("21.7.3", None),
+            ("20.11.1", Some("Iron")),
+            ("18.19.1", Some("Hydrogen")),
+            ("18.2.0", None),
+            ("18.1.0", None),
+            ("16.20.2", Some("Gallium")),
+        ]);
+
+        VersionInputs::parse_builtin(input)
+            .unwrap()
+            .select_release(&releases)
+            .map(|release| release.version.to_string())
+    }
+
+    #[test]
+    fn selects_newest_release_in_range() {
+        assert_eq!(select("^18.2").as_deref(), Some("18.19.1"));
+        assert_eq!(select(">=20 <22").as_deref(), Some("21.7.3"));
+        assert_eq!(select("18.x").as_deref(), Some("18.19.1"));
+        assert_eq!(select("v18.2.0").as_deref(), Some("18.2.0"));
+    }
+
+    #[test]
+    fn major_range_does_not_match_longer_majors() {
+        assert_eq!(select("1"), None);
+        assert_eq!(select("8"), None);
+    }
+
+    #[test]
+    fn selects_lts_lines() {
+        assert_eq!(select("lts").as_deref(), Some("20.11.1"));
+        assert_eq!(select("hydrogen").as_deref(), Some("18.19.1"));
+        assert_eq!(select("latest").as_deref(), Some("22.0.0"));
+        assert_eq!(select("unknown"), None);
+    }
+
+    #[test]
+    fn parsing_alias_name_leaves_resolution_to_run() {
+        let cli = Cli::try_parse_from(["nue", "--libc", "musl", "install", "work"]).unwrap();
+        let Subcommands::Install(install) = cli.subcommand else {
+            panic!("expected the install subcommand");
+        };
+
+        assert!(matches!(install.version, Some(VersionInputs::Lts(Some(name))) if name == "work"));
+        assert!(LIBC.get().is_none());
     }
 }
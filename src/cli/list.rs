@@ -1,15 +1,31 @@
-use std::{fmt, str};
+use core::sync::atomic::Ordering;
+
+use std::{
+    fmt,
+    io::{self, IsTerminal as _},
+    path, str,
+};
 
 use clap::Args;
-use demand::{DemandOption, Select, Spinner};
+use demand::{DemandOption, Select};
+use serde::Serialize;
 
-use crate::types;
+use crate::{
+    constants::BYTES_PER_MB,
+    globals::QUIET,
+    types::{
+        self,
+        node::{Channel, InstalledRelease},
+        platforms::Platform,
+    },
+    utils::spinner,
+};
 
-use super::{NueCommand, install};
+use super::{NueCommand, install, outdated};
 
 #[derive(Debug, Default, Clone)]
 enum VersionInputs {
-    VersionString(String),
+    VersionString(node_semver::Range),
     #[default]
     All,
     Lts(Option<String>),
@@ -21,49 +37,181 @@ pub struct CommandArguments {
     #[arg(default_value_t = VersionInputs::default())]
     version: VersionInputs,
 
+    /// List installed versions instead of available ones.
+    #[arg(long)]
+    installed: bool,
+
+    /// List builds from another release channel. Installed builds of every
+    /// channel are listed when omitted.
+    #[arg(long, value_enum)]
+    channel: Option<Channel>,
+
+    /// Only list releases bundling an npm version in this range.
+    #[arg(long, conflicts_with = "installed")]
+    npm: Option<node_semver::Range>,
+
+    /// Only list releases with this `NODE_MODULE_VERSION`, the native addon ABI.
+    #[arg(long, conflicts_with = "installed")]
+    modules: Option<String>,
+
+    /// Print releases as JSON instead of prompting.
+    #[arg(long)]
+    json: bool,
+
     /// Force install.
     #[arg(long)]
     force: bool,
+
+    /// Skip verifying the signature of release checksums.
+    #[arg(long)]
+    skip_signature: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct ReleaseOutput<'a> {
+    version: String,
+    lts: Option<&'a str>,
+    date: chrono::NaiveDate,
+    npm: Option<String>,
+    v8: Option<&'a str>,
+    uv: Option<&'a str>,
+    zlib: Option<&'a str>,
+    openssl: Option<&'a str>,
+    modules: Option<&'a str>,
+    security: bool,
+    supported: bool,
+    installed: bool,
+    active: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct InstalledReleaseOutput {
+    version: String,
+    channel: &'static str,
+    platform: String,
+    lts: Option<String>,
+    path: path::PathBuf,
+    size: u64,
+    active: bool,
 }
 
 impl NueCommand for CommandArguments {
     fn run(&self) -> anyhow::Result<()> {
+        if self.json {
+            QUIET.store(true, Ordering::Relaxed);
+        }
+
+        if self.installed {
+            return self.list_installed();
+        }
+
         let mut releases: Vec<types::node::Release> = Vec::new();
-        Spinner::new("Fetching releases...").run(|spinner| -> anyhow::Result<()> {
-            let all_releases = types::node::Release::get_all_releases()?;
+        let mut security_warning: Option<String> = None;
+        spinner::run("Fetching releases...", |spinner| -> anyhow::Result<()> {
+            let all_releases = types::node::Release::get_all_releases(
+                self.channel.unwrap_or_default(),
+                Platform::require_current()?,
+            )?;
+
+            // Security releases are only published on the release channel, so
+            // the active release is checked against it whichever one is listed.
+            if let Some(active) = InstalledRelease::get_all_installed()?
+                .into_iter()
+                .find(|release| release.is_active() && release.channel == Channel::Release)
+            {
+                let fetched_releases;
+                let stable_releases = if self.channel.unwrap_or_default() == Channel::Release {
+                    Some(all_releases.as_slice())
+                } else {
+                    fetched_releases =
+                        types::node::Release::get_all_releases(Channel::Release, active.platform)
+                            .ok();
+                    fetched_releases.as_deref()
+                };
+
+                security_warning = stable_releases
+                    .and_then(|releases| {
+                        types::node::Release::find_security_update(releases, &active.version)
+                    })
+                    .map(|release| outdated::security_warning(&active.version, release));
+            }
 
             spinner.title("Filtering releases...")?;
-            releases = all_releases.into_iter().filter(|release| {
-                    if !release.is_supported_by_current_platform() {
+            releases = all_releases
+                .into_iter()
+                .filter(|release| {
+                    if !self.json && !release.is_supported_by_current_platform() {
                         return false;
                     }
 
-                    match &self.version {
-                        VersionInputs::VersionString(version) => format!("{}", release.version).starts_with(version),
-                        VersionInputs::Lts(Some(code_name)) => matches!(&release.lts, types::node::Lts::CodeName(name) if *name.to_lowercase() == *code_name),
-                        VersionInputs::Lts(None) => release.lts.is_code_name(),
-                        VersionInputs::All => true,
-                    }
-                }).collect();
+                    self.version.matches(&release.version, &release.lts)
+                        && self.npm.as_ref().is_none_or(|range| {
+                            release.npm.as_ref().is_some_and(|npm| range.satisfies(npm))
+                        })
+                        && self
+                            .modules
+                            .as_ref()
+                            .is_none_or(|modules| release.modules.as_ref() == Some(modules))
+                })
+                .collect();
 
             Ok(())
-        })??;
+        })?;
+
+        if self.json {
+            let installed_releases = InstalledRelease::get_all_installed()?;
+            let output: Vec<_> = releases
+                .iter()
+                .map(|release| {
+                    let installed_release = installed_releases.iter().find(|installed| {
+                        installed.version == release.version && installed.channel == release.channel
+                    });
+
+                    ReleaseOutput {
+                        version: release.version.to_string(),
+                        lts: release.lts.code_name(),
+                        date: release.date,
+                        npm: release.npm.as_ref().map(ToString::to_string),
+                        v8: release.v8.as_deref(),
+                        uv: release.uv.as_deref(),
+                        zlib: release.zlib.as_deref(),
+                        openssl: release.openssl.as_deref(),
+                        modules: release.modules.as_deref(),
+                        security: release.security,
+                        supported: release.is_supported_by_current_platform(),
+                        installed: installed_release.is_some(),
+                        active: installed_release.is_some_and(InstalledRelease::is_active),
+                    }
+                })
+                .collect();
+
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        if let Some(security_warning) = &security_warning {
+            eprintln!("{security_warning}");
+        }
 
         if releases.is_empty() {
             anyhow::bail!("No release found with given version or LTS code name.");
-        } else if let Ok(selected_version) = Select::new("Select Node Version")
+        }
+
+        if !io::stdout().is_terminal() {
+            for release in &releases {
+                println!("{}", release_label(release));
+            }
+
+            return Ok(());
+        }
+
+        if let Ok(selected_version) = Select::new("Select Node Version")
             .filterable(true)
             .options(
                 releases
                     .iter()
                     .map(|release| {
-                        if release.lts.is_code_name() {
-                            DemandOption::new(&release.version).label(
-                                format!("v{} ({} LTS)", release.version, release.lts).as_str(),
-                            )
-                        } else {
-                            DemandOption::new(&release.version)
-                        }
+                        DemandOption::new(&release.version).label(release_label(release).as_str())
                     })
                     .collect(),
             )
@@ -75,8 +223,13 @@ impl NueCommand for CommandArguments {
                 .unwrap();
 
             install::CommandArguments {
-                version: install::VersionInputs::VersionString(release.version.to_string()),
+                version: Some(install::VersionInputs::exact(release)?),
+                from_file: None,
+                platform: None,
+                arch: None,
                 force: self.force,
+                skip_signature: self.skip_signature,
+                json: false,
             }
             .run()?;
         }
@@ -85,10 +238,101 @@ impl NueCommand for CommandArguments {
     }
 }
 
+impl CommandArguments {
+    fn list_installed(&self) -> anyhow::Result<()> {
+        let installed_releases: Vec<_> = InstalledRelease::get_all_installed()?
+            .into_iter()
+            .filter(|release| {
+                self.channel
+                    .is_none_or(|channel| release.channel == channel)
+                    && self.version.matches(&release.version, &release.lts())
+            })
+            .collect();
+
+        if self.json {
+            let output = installed_releases
+                .iter()
+                .map(|release| {
+                    Ok(InstalledReleaseOutput {
+                        version: release.version.to_string(),
+                        channel: release.channel.name(),
+                        platform: release.platform.node_platform_string(),
+                        lts: release.lts().code_name().map(str::to_string),
+                        path: release.path.clone(),
+                        size: release.disk_usage()?,
+                        active: release.is_active(),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            println!("{}", serde_json::to_string_pretty(&output)?);
+            return Ok(());
+        }
+
+        if installed_releases.is_empty() {
+            println!("No installed release found. Use `nue install` to install one.");
+            return Ok(());
+        }
+
+        let current = Platform::current();
+        for release in installed_releases {
+            let lts = release.lts();
+            let mut label = if lts.is_code_name() {
+                format!("v{} ({lts} LTS)", release.version)
+            } else {
+                format!("v{}", release.version)
+            };
+            if current != Some(release.platform) {
+                label.push_str(&format!(" [{}]", release.platform.node_platform_string()));
+            }
+
+            println!(
+                "{} {label:<24} {:>10.2}MiB",
+                if release.is_active() { '*' } else { ' ' },
+                release.disk_usage()? as f64 / BYTES_PER_MB
+            );
+        }
+
+        Ok(())
+    }
+}
+
+fn release_label(release: &types::node::Release) -> String {
+    let version = if release.lts.is_code_name() {
+        format!("v{} ({} LTS)", release.version, release.lts)
+    } else {
+        format!("v{}", release.version)
+    };
+
+    format!(
+        "{version:<24} {}  npm {:<8}  modules {:<4}{}",
+        release.date,
+        release
+            .npm
+            .as_ref()
+            .map_or_else(|| "-".to_string(), ToString::to_string),
+        release.modules.as_deref().unwrap_or("-"),
+        if release.security { "  security" } else { "" }
+    )
+}
+
+impl VersionInputs {
+    fn matches(&self, version: &node_semver::Version, lts: &types::node::Lts) -> bool {
+        match self {
+            Self::VersionString(range) => Channel::satisfies(range, version),
+            Self::Lts(Some(code_name)) => {
+                matches!(lts, types::node::Lts::CodeName(name) if name.to_lowercase() == *code_name)
+            }
+            Self::Lts(None) => lts.is_code_name(),
+            Self::All => true,
+        }
+    }
+}
+
 impl fmt::Display for VersionInputs {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::VersionString(version) => write!(f, "{version}"),
+            Self::VersionString(range) => write!(f, "{range}"),
             Self::All => write!(f, "all"),
             Self::Lts(Some(code_name)) => write!(f, "{code_name}"),
             Self::Lts(None) => write!(f, "lts"),
@@ -105,11 +349,11 @@ impl str::FromStr for VersionInputs {
         match s.as_str() {
             "all" => Ok(Self::All),
             "lts" => Ok(Self::Lts(None)),
-            _ if s.starts_with('v') && s[1..].parse::<node_semver::Range>().is_ok() => {
-                Ok(Self::VersionString(s[1..].to_string()))
-            }
-            _ if s.parse::<node_semver::Range>().is_ok() => Ok(Self::VersionString(s)),
-            _ => Ok(Self::Lts(Some(s))),
+            _ => Ok(s
+                .strip_prefix('v')
+                .unwrap_or(&s)
+                .parse::<node_semver::Range>()
+                .map_or_else(|_| Self::Lts(Some(s.clone())), Self::VersionString)),
         }
     }
 }
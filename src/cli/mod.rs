@@ -1,10 +1,17 @@
 use clap::{Parser, Subcommand};
 
+use crate::types::platforms::Libc;
+
+mod alias;
 mod clean;
 mod env;
+mod info;
 mod install;
 mod list;
+mod outdated;
 mod uninstall;
+mod upgrade;
+mod r#use;
 
 pub trait NueCommand {
     fn run(&self) -> anyhow::Result<()>;
@@ -15,17 +22,36 @@ pub trait NueCommand {
 pub struct Cli {
     #[command(subcommand)]
     pub subcommand: Subcommands,
+
+    /// Disable all network access. Can also be set with `NUE_OFFLINE`.
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Install releases built for this libc instead of the detected one. Linux only.
+    #[arg(long, global = true, value_enum)]
+    pub libc: Option<Libc>,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Subcommands {
     /// Install or update Node.
-    #[command(alias = "update", alias = "use")]
+    #[command(alias = "update")]
     Install(install::CommandArguments),
+    /// Switch to an installed Node version.
+    Use(r#use::CommandArguments),
     /// Uninstall Node.
     Uninstall(uninstall::CommandArguments),
     /// List all available Node versions.
+    #[command(alias = "ls")]
     List(list::CommandArguments),
+    /// Show details about a Node release.
+    Info(info::CommandArguments),
+    /// Check if the pinned or active Node version is outdated.
+    Outdated(outdated::CommandArguments),
+    /// Upgrade the active Node to the newest release in its major or LTS line.
+    Upgrade(upgrade::CommandArguments),
+    /// List, show, create or remove version aliases.
+    Alias(alias::CommandArguments),
     /// Generate environment script.
     Env(env::CommandArguments),
     /// Clean up downloaded releases cache.
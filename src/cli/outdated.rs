@@ -0,0 +1,137 @@
+use core::sync::atomic::Ordering;
+
+use clap::Args;
+use serde::Serialize;
+
+use crate::{
+    globals::QUIET,
+    types::{
+        node::{Channel, InstalledRelease, Release},
+        platforms::Platform,
+    },
+    utils::{project, spinner},
+};
+
+use super::{NueCommand, install::VersionInputs};
+
+#[derive(Args, Debug)]
+pub struct CommandArguments {
+    /// Version, range or LTS code name of Node to check, ranges at their newest
+    /// release. Defaults to the version pinned in `.node-version`, `.nvmrc` or
+    /// `package.json`, or the active one.
+    version: Option<String>,
+
+    /// Exit with an error if the version is behind a security release.
+    #[arg(long)]
+    fail_on_security: bool,
+
+    /// Print the result as JSON.
+    #[arg(long)]
+    json: bool,
+}
+
+#[derive(Serialize, Debug)]
+struct OutdatedOutput {
+    version: String,
+    source: String,
+    upgrade: Option<String>,
+    security_update: Option<String>,
+}
+
+impl NueCommand for CommandArguments {
+    fn run(&self) -> anyhow::Result<()> {
+        if self.json {
+            QUIET.store(true, Ordering::Relaxed);
+        }
+
+        let releases = spinner::run("Fetching releases...", |_| {
+            Release::get_all_releases(Channel::Release, Platform::require_current()?)
+        })?;
+
+        let (version, source) = self.checked_version(&releases)?;
+        let upgrade = Release::find_upgrade(&releases, &version);
+        let security_update = Release::find_security_update(&releases, &version);
+
+        if self.json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&OutdatedOutput {
+                    version: version.to_string(),
+                    source,
+                    upgrade: upgrade.map(|release| release.version.to_string()),
+                    security_update: security_update.map(|release| release.version.to_string()),
+                })?
+            );
+        } else if let Some(security_update) = security_update {
+            println!("Node v{version} ({source}) is outdated.");
+            println!("{}", security_warning(&version, security_update));
+        } else if let Some(upgrade) = upgrade {
+            println!(
+                "Node v{version} ({source}) is outdated, v{0} is the newest in its line. Run `nue install {0}` to install it.",
+                upgrade.version
+            );
+        } else {
+            println!("Node v{version} ({source}) is up to date.");
+        }
+
+        if self.fail_on_security && security_update.is_some() {
+            anyhow::bail!("Node v{version} is behind a security release.");
+        }
+
+        Ok(())
+    }
+}
+
+impl CommandArguments {
+    fn checked_version(
+        &self,
+        releases: &[Release],
+    ) -> anyhow::Result<(node_semver::Version, String)> {
+        if let Some(version) = &self.version {
+            return Ok((resolve_pin(version, releases)?, "given".to_string()));
+        }
+
+        if let Some((version, path)) = project::find_node_version()? {
+            return Ok((
+                resolve_pin(&version, releases)?,
+                format!("pinned as `{version}` in `{}`", path.display()),
+            ));
+        }
+
+        InstalledRelease::get_all_installed()?
+            .into_iter()
+            .find(InstalledRelease::is_active)
+            .map(|release| (release.version, "active".to_string()))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No pinned or active Node version to check. Pass a version to check instead."
+                )
+            })
+    }
+}
+
+/// Ranges and LTS code names are checked at the newest release they match,
+/// which is what installing them gets.
+fn resolve_pin(pin: &str, releases: &[Release]) -> anyhow::Result<node_semver::Version> {
+    let input = VersionInputs::parse_builtin(pin)?.resolve_alias()?;
+    if input.channel() != Channel::Release {
+        anyhow::bail!(
+            "`{pin}` is a {} build, only stable releases can be checked.",
+            input.channel()
+        );
+    }
+
+    input
+        .select_release(releases)
+        .map(|release| release.version.clone())
+        .ok_or_else(|| {
+            anyhow::anyhow!("`{pin}` doesn't match any release, so it can't be checked.")
+        })
+}
+
+pub fn security_warning(version: &node_semver::Version, security_update: &Release) -> String {
+    format!(
+        "Node v{version} has known security issues fixed in v{0}. Run `nue install {0}` to update.",
+        security_update.version
+    )
+}
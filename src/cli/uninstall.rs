@@ -1,44 +1,148 @@
 use std::fs;
 
 use clap::Args;
+use demand::{DemandOption, MultiSelect};
 
-use crate::{globals::NUE_PATH, utils};
+use crate::{
+    globals::NUE_PATH,
+    types::{
+        node::{Channel, InstalledRelease},
+        platforms::Platform,
+    },
+    utils,
+};
 
-use super::NueCommand;
+use super::{NueCommand, install::VersionInputs};
 
 #[derive(Args, Debug)]
-pub struct CommandArguments;
+pub struct CommandArguments {
+    /// Optional version or range of Node to uninstall. Prompts to select
+    /// installed versions when omitted.
+    version: Option<VersionInputs>,
+
+    /// Uninstall the version that is currently in use.
+    #[arg(long)]
+    force: bool,
+}
 
 impl NueCommand for CommandArguments {
     fn run(&self) -> anyhow::Result<()> {
-        let nue_node_path = NUE_PATH.join("node");
+        let installed_releases = InstalledRelease::get_all_installed()?;
+
+        if installed_releases.is_empty() {
+            println!("Node is not installed.");
+            print_path_help()?;
+
+            return Ok(());
+        }
 
-        if nue_node_path.try_exists()? {
-            fs::remove_dir_all(nue_node_path)?;
-            println!("Node uninstalled successfully.");
+        let version = self
+            .version
+            .clone()
+            .map(VersionInputs::resolve_alias)
+            .transpose()?;
+        let selected_releases: Vec<&InstalledRelease> = match &version {
+            Some(version @ (VersionInputs::Latest | VersionInputs::Channel(_, None))) => {
+                installed_releases
+                    .iter()
+                    .find(|release| version.matches_installed(release))
+                    .into_iter()
+                    .collect()
+            }
+            Some(version) => installed_releases
+                .iter()
+                .filter(|release| version.matches_installed(release))
+                .collect(),
+            None => {
+                let current = Platform::current();
+                let selected_indices = MultiSelect::new("Select Node Versions to Uninstall")
+                    .filterable(true)
+                    .options(
+                        installed_releases
+                            .iter()
+                            .enumerate()
+                            .map(|(index, release)| {
+                                let mut label = format!("v{}", release.version);
+                                if release.channel != Channel::Release {
+                                    label.push_str(&format!(" ({})", release.channel));
+                                }
+                                if current != Some(release.platform) {
+                                    label.push_str(&format!(
+                                        " [{}]",
+                                        release.platform.node_platform_string()
+                                    ));
+                                }
+                                if release.is_active() {
+                                    label.push_str(" (active)");
+                                }
+
+                                DemandOption::new(index).label(&label)
+                            })
+                            .collect(),
+                    )
+                    .run()?;
+
+                selected_indices
+                    .into_iter()
+                    .filter_map(|index| installed_releases.get(index))
+                    .collect()
+            }
+        };
+
+        if selected_releases.is_empty() {
+            if self.version.is_some() {
+                anyhow::bail!("No installed release found with given version or LTS code name.");
+            }
 
+            println!("Nothing to uninstall.");
             return Ok(());
         }
 
-        println!("Node is not installed.");
+        if !self.force {
+            if let Some(release) = selected_releases.iter().find(|release| release.is_active()) {
+                anyhow::bail!(
+                    "Node v{} is currently in use. Use `--force` to uninstall it anyway.",
+                    release.version
+                );
+            }
+        }
 
-        if utils::check::is_node_in_path() {
-            #[cfg(unix)]
-            let platform_specific_help = format!(
-                "Remove the sourced env script from your shell profile ({}).",
-                files_in_home_containing("$HOME/.nue/env")?.join(", ")
-            );
-            #[cfg(windows)]
-            let platform_specific_help =
-                r"Remove the entry that ends with `\nue\node` from your user `Path`.".to_string();
+        let mut uninstalled_active = false;
+        for release in selected_releases {
+            if release.is_active() {
+                fs::remove_dir_all(NUE_PATH.join("node"))?;
+                uninstalled_active = true;
+            }
+
+            fs::remove_dir_all(&release.path)?;
+            println!("Node v{} uninstalled successfully.", release.version);
+        }
 
-            println!("Node is still in your `PATH`. {platform_specific_help}");
+        if uninstalled_active {
+            print_path_help()?;
         }
 
         Ok(())
     }
 }
 
+fn print_path_help() -> anyhow::Result<()> {
+    if utils::check::is_node_in_path() {
+        #[cfg(unix)]
+        let platform_specific_help = format!(
+            "Remove the sourced env script from your shell profile ({}).",
+            files_in_home_containing("$HOME/.nue/env")?.join(", ")
+        );
+        #[cfg(windows)]
+        let platform_specific_help =
+            r"Remove the entry that ends with `\nue\node` from your user `Path`.".to_string();
+
+        println!("Node is still in your `PATH`. {platform_specific_help}");
+    }
+
+    Ok(())
+}
+
 #[cfg(unix)]
 fn files_in_home_containing(substring: &str) -> anyhow::Result<Vec<String>> {
     let home_dir =
@@ -0,0 +1,69 @@
+use std::fs;
+
+use clap::Args;
+
+use crate::{
+    types::node::{InstalledRelease, Release},
+    utils::spinner,
+};
+
+use super::{NueCommand, install};
+
+#[derive(Args, Debug)]
+pub struct CommandArguments {
+    /// Remove the previously active release after upgrading.
+    #[arg(long)]
+    remove_old: bool,
+
+    /// Skip verifying the signature of release checksums.
+    #[arg(long)]
+    skip_signature: bool,
+}
+
+impl NueCommand for CommandArguments {
+    fn run(&self) -> anyhow::Result<()> {
+        let active_release = InstalledRelease::get_all_installed()?
+            .into_iter()
+            .find(InstalledRelease::is_active)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No active Node release to upgrade. Use `nue install` to install one."
+                )
+            })?;
+
+        let releases = spinner::run("Fetching releases...", |_| -> anyhow::Result<_> {
+            Ok(
+                Release::get_all_releases(active_release.channel, active_release.platform)?
+                    .into_iter()
+                    .filter(|release| release.is_supported_by(active_release.platform))
+                    .collect::<Vec<_>>(),
+            )
+        })?;
+
+        let Some(upgrade) = Release::find_upgrade(&releases, &active_release.version) else {
+            println!(
+                "Node v{} is already the newest in its line.",
+                active_release.version
+            );
+            return Ok(());
+        };
+
+        install::CommandArguments {
+            version: Some(install::VersionInputs::exact(upgrade)?),
+            from_file: None,
+            platform: Some(active_release.platform.node_os().to_string()),
+            arch: Some(active_release.platform.node_arch().to_string()),
+            force: false,
+            skip_signature: self.skip_signature,
+            json: false,
+        }
+        .run()?;
+
+        if self.remove_old {
+            fs::remove_dir_all(&active_release.path)?;
+            println!("Removed Node v{}.", active_release.version);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use clap::Args;
+
+use crate::{types, utils};
+
+use super::{NueCommand, install::VersionInputs};
+
+#[derive(Args, Debug)]
+pub struct CommandArguments {
+    /// Optional version of installed Node to use. Defaults to the version in
+    /// `.node-version`, `.nvmrc` or `package.json`, the `default` alias, or the
+    /// latest installed one.
+    version: Option<VersionInputs>,
+}
+
+impl NueCommand for CommandArguments {
+    fn run(&self) -> anyhow::Result<()> {
+        let version = VersionInputs::resolve(self.version.as_ref())?;
+        let installed_releases = types::node::InstalledRelease::get_all_installed()?;
+
+        let selected_release = installed_releases
+            .iter()
+            .filter(|release| release.platform.is_runnable())
+            .find(|release| version.matches_installed(release));
+
+        match selected_release {
+            Some(release) => {
+                release.link()?;
+                println!("Now using Node v{}.", release.version);
+
+                if !utils::check::is_node_in_path() {
+                    println!(
+                        "Node is installed, but its path isn't in `PATH`. Run `nue env` to fix it."
+                    );
+                }
+            }
+            None => {
+                anyhow::bail!(
+                    "No installed release found with given version. Use `nue install {version}` to install it."
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
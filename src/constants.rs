@@ -1,3 +1,7 @@
 pub const NODE_DISTRIBUTIONS_URL: &str = "https://nodejs.org/dist";
-pub const NODE_DISTRIBUTIONS_INDEX_URL: &str = "https://nodejs.org/dist/index.json";
+pub const NODE_DOWNLOADS_URL: &str = "https://nodejs.org/download";
+pub const NODE_UNOFFICIAL_DISTRIBUTIONS_URL: &str =
+    "https://unofficial-builds.nodejs.org/download/release";
 pub const NODE_GITHUB_URL: &str = "https://github.com/nodejs/node";
+
+pub const BYTES_PER_MB: f64 = 1_048_576.0;
@@ -1,4 +1,11 @@
-use std::{path::PathBuf, sync::LazyLock};
+use core::sync::atomic::AtomicBool;
+
+use std::{
+    path::PathBuf,
+    sync::{LazyLock, OnceLock},
+};
+
+use crate::types::{config::Config, platforms::Libc};
 
 pub static NUE_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
     #[cfg(unix)]
@@ -16,3 +23,19 @@ pub static NUE_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
 });
 
 pub static NUE_RELEASES_PATH: LazyLock<PathBuf> = LazyLock::new(|| NUE_PATH.join("releases"));
+
+pub static NUE_ALIASES_PATH: LazyLock<PathBuf> = LazyLock::new(|| NUE_PATH.join("aliases"));
+
+pub static NUE_CACHE_PATH: LazyLock<PathBuf> = LazyLock::new(|| NUE_PATH.join("cache"));
+
+pub static NUE_DOWNLOADS_PATH: LazyLock<PathBuf> = LazyLock::new(|| NUE_PATH.join("downloads"));
+
+/// Loaded in `main` before any command runs, so a broken config file fails
+/// the command instead of being silently replaced by defaults.
+pub static CONFIG: OnceLock<Config> = OnceLock::new();
+
+pub static QUIET: AtomicBool = AtomicBool::new(false);
+
+pub static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+pub static LIBC: OnceLock<Libc> = OnceLock::new();
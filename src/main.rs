@@ -1,3 +1,7 @@
+use core::sync::atomic::Ordering;
+
+use std::env;
+
 use clap::Parser as _;
 
 use cli::NueCommand as _;
@@ -10,10 +14,33 @@ mod types;
 mod utils;
 
 fn main() -> anyhow::Result<()> {
-    match cli::Cli::parse().subcommand {
+    let cli = cli::Cli::parse();
+
+    if cli.offline
+        || env::var("NUE_OFFLINE").is_ok_and(|value| !matches!(value.as_str(), "" | "0" | "false"))
+    {
+        globals::OFFLINE.store(true, Ordering::Relaxed);
+    }
+
+    globals::CONFIG
+        .set(types::config::Config::load()?)
+        .expect("config is set before anything reads it");
+
+    if let Some(libc) = cli.libc {
+        globals::LIBC
+            .set(libc)
+            .expect("libc is set before anything reads it");
+    }
+
+    match cli.subcommand {
         cli::Subcommands::Install(install) => install.run(),
+        cli::Subcommands::Use(r#use) => r#use.run(),
         cli::Subcommands::Uninstall(uninstall) => uninstall.run(),
         cli::Subcommands::List(list) => list.run(),
+        cli::Subcommands::Info(info) => info.run(),
+        cli::Subcommands::Outdated(outdated) => outdated.run(),
+        cli::Subcommands::Upgrade(upgrade) => upgrade.run(),
+        cli::Subcommands::Alias(alias) => alias.run(),
         cli::Subcommands::Env(env) => env.run(),
         cli::Subcommands::Clean(clean) => clean.run(),
     }?;
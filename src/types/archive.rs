@@ -0,0 +1,46 @@
+use std::{fs, path};
+
+use binstall_tar::Archive;
+use flate2::read::GzDecoder;
+use liblzma::read::XzDecoder;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    TarXz,
+    TarGz,
+    Zip,
+    SevenZip,
+}
+
+impl ArchiveFormat {
+    pub const ALL: [Self; 4] = [Self::TarXz, Self::TarGz, Self::Zip, Self::SevenZip];
+
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::TarXz => "tar.xz",
+            Self::TarGz => "tar.gz",
+            Self::Zip => "zip",
+            Self::SevenZip => "7z",
+        }
+    }
+
+    pub fn split_file_name(file_name: &str) -> Option<(&str, Self)> {
+        Self::ALL.into_iter().find_map(|format| {
+            file_name
+                .strip_suffix(format.extension())?
+                .strip_suffix('.')
+                .map(|stem| (stem, format))
+        })
+    }
+
+    pub fn unpack_file(self, file: fs::File, destination: &path::Path) -> anyhow::Result<()> {
+        match self {
+            Self::TarXz => Archive::new(XzDecoder::new(file)).unpack(destination)?,
+            Self::TarGz => Archive::new(GzDecoder::new(file)).unpack(destination)?,
+            Self::Zip => zip::ZipArchive::new(file)?.extract(destination)?,
+            Self::SevenZip => sevenz_rust2::decompress(file, destination)?,
+        }
+
+        Ok(())
+    }
+}
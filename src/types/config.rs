@@ -0,0 +1,126 @@
+use std::{env, fs, path};
+
+use anyhow::Context as _;
+use serde::Deserialize;
+
+use crate::{
+    constants::{NODE_DISTRIBUTIONS_URL, NODE_DOWNLOADS_URL, NODE_UNOFFICIAL_DISTRIBUTIONS_URL},
+    globals::{CONFIG, NUE_PATH},
+};
+
+use super::platforms::{Libc, Platform};
+
+const MIRROR_ENVIRONMENT_VARIABLES: [&str; 2] = ["NUE_NODE_MIRROR", "NVM_NODEJS_ORG_MIRROR"];
+
+const DEFAULT_INDEX_TTL: u64 = 60 * 60;
+
+#[derive(Deserialize, Debug)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Config {
+    pub mirror: Option<String>,
+    pub index_url: Option<String>,
+    pub musl_mirror: Option<String>,
+    pub channel_mirror: Option<String>,
+    pub libc: Option<Libc>,
+    pub index_ttl: u64,
+    pub proxy: Option<String>,
+    pub ca_file: Option<path::PathBuf>,
+    pub auth_token: Option<String>,
+    pub auth_username: Option<String>,
+    pub auth_password: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mirror: None,
+            index_url: None,
+            musl_mirror: None,
+            channel_mirror: None,
+            libc: None,
+            index_ttl: DEFAULT_INDEX_TTL,
+            proxy: None,
+            ca_file: None,
+            auth_token: None,
+            auth_username: None,
+            auth_password: None,
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> anyhow::Result<Self> {
+        let config_path = NUE_PATH.join("config.toml");
+
+        let mut config: Self = if config_path.try_exists()? {
+            toml::from_str(&fs::read_to_string(&config_path)?)
+                .with_context(|| format!("Failed to parse `{}`.", config_path.display()))?
+        } else {
+            Self::default()
+        };
+
+        if let Some(mirror) = MIRROR_ENVIRONMENT_VARIABLES
+            .into_iter()
+            .find_map(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+        {
+            config.mirror = Some(mirror);
+            config.index_url = None;
+        }
+
+        if let Ok(token) = env::var("NUE_MIRROR_TOKEN") {
+            config.auth_token = Some(token);
+        }
+
+        Ok(config)
+    }
+
+    pub fn get() -> &'static Self {
+        CONFIG.get().expect("config is loaded in main")
+    }
+
+    /// Musl builds aren't published on the official mirror, so they are
+    /// fetched from the unofficial builds project instead.
+    pub fn mirror_url(&self, platform: Platform) -> &str {
+        match platform {
+            Platform::LinuxMusl(_) => self
+                .musl_mirror
+                .as_deref()
+                .unwrap_or(NODE_UNOFFICIAL_DISTRIBUTIONS_URL),
+            _ => self.mirror.as_deref().unwrap_or(NODE_DISTRIBUTIONS_URL),
+        }
+        .trim_end_matches('/')
+    }
+
+    /// Base URL of the non-release channels, each served from
+    /// `<channel-mirror>/<channel>`.
+    pub fn channel_mirror_url(&self) -> &str {
+        self.channel_mirror
+            .as_deref()
+            .unwrap_or(NODE_DOWNLOADS_URL)
+            .trim_end_matches('/')
+    }
+
+    /// Whether `url` is served from a mirror or index the user configured.
+    /// Credentials are only sent there, never to the public defaults.
+    pub fn is_configured_url(&self, url: &str) -> bool {
+        let is_under = |base: &str| {
+            url.strip_prefix(base.trim_end_matches('/'))
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+
+        [&self.mirror, &self.musl_mirror, &self.channel_mirror]
+            .into_iter()
+            .flatten()
+            .any(|mirror| is_under(mirror))
+            || self.index_url.as_deref() == Some(url)
+    }
+
+    pub fn index_url(&self, platform: Platform) -> String {
+        match (platform, &self.index_url) {
+            (Platform::LinuxMusl(_), _) | (_, None) => {
+                format!("{}/index.json", self.mirror_url(platform))
+            }
+            (_, Some(index_url)) => index_url.clone(),
+        }
+    }
+}
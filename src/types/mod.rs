@@ -1,2 +1,4 @@
+pub mod archive;
+pub mod config;
 pub mod node;
 pub mod platforms;
@@ -0,0 +1,136 @@
+use std::fmt;
+
+use clap::ValueEnum;
+
+use crate::types::{config::Config, platforms::Platform};
+
+#[derive(ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Channel {
+    #[default]
+    Release,
+    Nightly,
+    Rc,
+    V8Canary,
+    Test,
+}
+
+impl Channel {
+    pub const ALL: [Self; 5] = [
+        Self::Release,
+        Self::Nightly,
+        Self::Rc,
+        Self::V8Canary,
+        Self::Test,
+    ];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Release => "release",
+            Self::Nightly => "nightly",
+            Self::Rc => "rc",
+            Self::V8Canary => "v8-canary",
+            Self::Test => "test",
+        }
+    }
+
+    /// Splits inputs like `nightly` or `rc/22` into their channel and the
+    /// rest of the version input.
+    pub fn split_version_input(input: &str) -> Option<(Self, Option<&str>)> {
+        Self::ALL
+            .into_iter()
+            .filter(|channel| *channel != Self::Release)
+            .find_map(|channel| {
+                let rest = input.strip_prefix(channel.name())?;
+                if rest.is_empty() {
+                    return Some((channel, None));
+                }
+
+                rest.strip_prefix('/')
+                    .map(|version| (channel, Some(version)))
+            })
+    }
+
+    pub fn distribution_url(self, platform: Platform) -> String {
+        match self {
+            Self::Release => Config::get().mirror_url(platform).to_string(),
+            _ => format!("{}/{}", Config::get().channel_mirror_url(), self.name()),
+        }
+    }
+
+    pub fn index_url(self, platform: Platform) -> String {
+        match self {
+            Self::Release => Config::get().index_url(platform),
+            _ => format!("{}/index.json", self.distribution_url(platform)),
+        }
+    }
+
+    /// Only stable and release candidate checksums are signed by releasers.
+    pub const fn is_signed(self) -> bool {
+        matches!(self, Self::Release | Self::Rc)
+    }
+
+    /// Name of the directory a release is stored in under `NUE_RELEASES_PATH`.
+    /// Channel builds are prefixed so they never collide with stable ones.
+    pub fn release_directory_name(
+        self,
+        version: &node_semver::Version,
+        platform: Platform,
+    ) -> String {
+        match self {
+            Self::Release => format!("node-v{version}-{}", platform.node_platform_string()),
+            _ => format!(
+                "node-{}-v{version}-{}",
+                self.name(),
+                platform.node_platform_string()
+            ),
+        }
+    }
+
+    /// Channel builds are pre-releases, which semver ranges only match when
+    /// they name the exact pre-release. Other ranges are matched against the
+    /// version they are a pre-release of.
+    pub fn satisfies(range: &node_semver::Range, version: &node_semver::Version) -> bool {
+        range.satisfies(version)
+            || range.satisfies(&node_semver::Version {
+                major: version.major,
+                minor: version.minor,
+                patch: version.patch,
+                build: vec![],
+                pre_release: vec![],
+            })
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Channel;
+
+    #[test]
+    fn splits_channel_version_inputs() {
+        assert_eq!(
+            Channel::split_version_input("rc/22"),
+            Some((Channel::Rc, Some("22")))
+        );
+        assert_eq!(
+            Channel::split_version_input("nightly"),
+            Some((Channel::Nightly, None))
+        );
+        assert_eq!(
+            Channel::split_version_input("v8-canary/22.0.0"),
+            Some((Channel::V8Canary, Some("22.0.0")))
+        );
+    }
+
+    #[test]
+    fn leaves_other_inputs_alone() {
+        assert_eq!(Channel::split_version_input("22"), None);
+        assert_eq!(Channel::split_version_input("rcx"), None);
+        assert_eq!(Channel::split_version_input("release/22"), None);
+    }
+}
@@ -0,0 +1,317 @@
+use std::{ffi, fs, path};
+
+use crate::{
+    globals::{NUE_PATH, NUE_RELEASES_PATH},
+    types::{archive::ArchiveFormat, platforms::Platform},
+    utils::{cache, check, link},
+};
+
+use super::{Channel, Lts};
+
+#[derive(Clone, Debug)]
+pub struct InstalledRelease {
+    pub version: node_semver::Version,
+    pub channel: Channel,
+    pub platform: Platform,
+    pub path: path::PathBuf,
+}
+
+impl InstalledRelease {
+    pub fn from_path(path: path::PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.strip_prefix("node-")?;
+        let (channel, name) = Channel::ALL
+            .into_iter()
+            .filter(|channel| *channel != Channel::Release)
+            .find_map(|channel| {
+                name.strip_prefix(channel.name())?
+                    .strip_prefix('-')
+                    .map(|name| (channel, name))
+            })
+            .unwrap_or((Channel::Release, name));
+
+        let (version, platform) = Platform::split_release_name(name.strip_prefix('v')?)?;
+
+        Some(Self {
+            version: version.parse().ok()?,
+            channel,
+            platform,
+            path,
+        })
+    }
+
+    pub fn get_all_installed() -> anyhow::Result<Vec<Self>> {
+        let mut installed_releases: Vec<Self> = cache::find_cached_node_downloads()?
+            .into_iter()
+            .filter_map(Self::from_path)
+            .collect();
+
+        // Native builds come first among equal versions, so they are preferred
+        // over ones running under emulation.
+        let current = Platform::current();
+        installed_releases.sort_by(|a, b| {
+            b.version
+                .cmp(&a.version)
+                .then_with(|| (Some(b.platform) == current).cmp(&(Some(a.platform) == current)))
+        });
+        Ok(installed_releases)
+    }
+
+    pub fn install_from_file(source: &path::Path) -> anyhow::Result<Self> {
+        let file_name = source
+            .file_name()
+            .and_then(ffi::OsStr::to_str)
+            .ok_or_else(|| anyhow::anyhow!("`{}` isn't a valid file name.", source.display()))?;
+
+        let (release_name, format) = if source.is_dir() {
+            (file_name, None)
+        } else {
+            let (stem, format) = ArchiveFormat::split_file_name(file_name).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "`{file_name}` isn't a supported archive, expected a `.tar.xz`, `.tar.gz`, `.zip` or `.7z` file."
+                )
+            })?;
+            (stem, Some(format))
+        };
+
+        let release = Self::from_path(NUE_RELEASES_PATH.join(release_name)).ok_or_else(|| {
+            anyhow::anyhow!(
+                "`{file_name}` isn't named like a Node release, expected `node-v<version>-<platform>`."
+            )
+        })?;
+
+        if !NUE_RELEASES_PATH.try_exists()? {
+            fs::create_dir_all(&*NUE_RELEASES_PATH)?;
+        }
+
+        let staging_directory = tempfile::Builder::new()
+            .prefix(".staging-")
+            .tempdir_in(&*NUE_RELEASES_PATH)?;
+        match format {
+            Some(format) => {
+                format.unpack_file(fs::File::open(source)?, staging_directory.path())?
+            }
+            None => copy_directory(source, &staging_directory.path().join(release_name))?,
+        }
+
+        let runnable = release.platform.is_runnable();
+        release.promote_staged(staging_directory.path(), release_name, runnable)?;
+        if runnable {
+            release.link()?;
+        }
+
+        Ok(release)
+    }
+
+    /// Validates the release unpacked as `staged_name` in `staging_directory`
+    /// and moves it into place. A previous install is moved aside and only
+    /// removed once the new one is in place, or moved back if that fails.
+    pub fn promote_staged(
+        &self,
+        staging_directory: &path::Path,
+        staged_name: &str,
+        validate: bool,
+    ) -> anyhow::Result<()> {
+        let staged_release_path = staging_directory.join(staged_name);
+        if validate {
+            check::validate_node_release(&staged_release_path, &self.version)?;
+        } else if !staged_release_path.is_dir() {
+            anyhow::bail!("The release archive doesn't contain `{staged_name}`.");
+        }
+
+        // Kept outside `staging_directory`, which is deleted when dropped, and
+        // named so `nue clean` finds it if it can't be moved back.
+        let replaced_path = self.path.with_file_name(format!(
+            ".staging-replaced-{}",
+            self.path.file_name().unwrap_or_default().to_string_lossy()
+        ));
+        let replacing = self.path.try_exists()?;
+        if replacing {
+            if replaced_path.try_exists()? {
+                fs::remove_dir_all(&replaced_path)?;
+            }
+            fs::rename(&self.path, &replaced_path)?;
+        }
+
+        if let Err(error) = fs::rename(&staged_release_path, &self.path) {
+            if replacing {
+                fs::rename(&replaced_path, &self.path).map_err(|restore_error| {
+                    anyhow::anyhow!(
+                        "Failed to move the release into place ({error}), and restoring the previous one failed: {restore_error}. It is kept at `{}`.",
+                        replaced_path.display()
+                    )
+                })?;
+            }
+
+            return Err(error.into());
+        }
+
+        if replacing {
+            fs::remove_dir_all(&replaced_path)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn link(&self) -> anyhow::Result<()> {
+        link::link_node_release(&self.path)
+    }
+
+    pub fn is_active(&self) -> bool {
+        NUE_PATH
+            .join("node")
+            .read_link()
+            .is_ok_and(|linked_path| linked_path == self.path)
+    }
+
+    pub fn lts(&self) -> Lts {
+        fs::read_to_string(
+            self.path
+                .join("include")
+                .join("node")
+                .join("node_version.h"),
+        )
+        .ok()
+        .and_then(|header| {
+            header.lines().find_map(|line| {
+                line.strip_prefix("#define NODE_VERSION_LTS_CODENAME")
+                    .map(|code_name| code_name.trim().trim_matches('"').to_string())
+                    .filter(|code_name| !code_name.is_empty())
+            })
+        })
+        .map_or(Lts::False, Lts::CodeName)
+    }
+
+    pub fn disk_usage(&self) -> anyhow::Result<u64> {
+        directory_size(&self.path)
+    }
+}
+
+fn directory_size(path: &path::Path) -> anyhow::Result<u64> {
+    let mut size = 0;
+
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+
+        if metadata.is_dir() {
+            size += directory_size(&entry.path())?;
+        } else {
+            size += metadata.len();
+        }
+    }
+
+    Ok(size)
+}
+
+fn copy_directory(source: &path::Path, destination: &path::Path) -> anyhow::Result<()> {
+    fs::create_dir_all(destination)?;
+
+    for entry in fs::read_dir(source)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let target = destination.join(entry.file_name());
+
+        if file_type.is_dir() {
+            copy_directory(&entry.path(), &target)?;
+        } else if file_type.is_symlink() {
+            #[cfg(unix)]
+            std::os::unix::fs::symlink(fs::read_link(entry.path())?, &target)?;
+            #[cfg(windows)]
+            fs::copy(entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path;
+
+    use crate::types::{
+        node::Channel,
+        platforms::{LinuxArch, LinuxMuslArch, MacArch, Platform, WindowsArch},
+    };
+
+    use super::InstalledRelease;
+
+    fn parse(name: &str) -> Option<(String, Channel, Platform)> {
+        InstalledRelease::from_path(path::PathBuf::from("releases").join(name)).map(|release| {
+            (
+                release.version.to_string(),
+                release.channel,
+                release.platform,
+            )
+        })
+    }
+
+    #[test]
+    fn parses_release_directories() {
+        assert_eq!(
+            parse("node-v8.17.0-linux-x64"),
+            Some((
+                "8.17.0".to_string(),
+                Channel::Release,
+                Platform::Linux(LinuxArch::X64)
+            ))
+        );
+        assert_eq!(
+            parse("node-v20.11.1-darwin-x64"),
+            Some((
+                "20.11.1".to_string(),
+                Channel::Release,
+                Platform::Mac(MacArch::X64)
+            ))
+        );
+        assert_eq!(
+            parse("node-v20.11.1-win-arm64"),
+            Some((
+                "20.11.1".to_string(),
+                Channel::Release,
+                Platform::Windows(WindowsArch::ARM64)
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_musl_suffix() {
+        assert_eq!(
+            parse("node-v20.11.1-linux-arm64-musl"),
+            Some((
+                "20.11.1".to_string(),
+                Channel::Release,
+                Platform::LinuxMusl(LinuxMuslArch::ARM64)
+            ))
+        );
+    }
+
+    #[test]
+    fn parses_channel_directories() {
+        assert_eq!(
+            parse("node-v8-canary-v22.0.0-v8-canary20240101abcdef-linux-x64"),
+            Some((
+                "22.0.0-v8-canary20240101abcdef".to_string(),
+                Channel::V8Canary,
+                Platform::Linux(LinuxArch::X64)
+            ))
+        );
+        assert_eq!(
+            parse("node-nightly-v23.0.0-nightly20240101abcdef-linux-x64-musl"),
+            Some((
+                "23.0.0-nightly20240101abcdef".to_string(),
+                Channel::Nightly,
+                Platform::LinuxMusl(LinuxMuslArch::X64)
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_other_directories() {
+        assert_eq!(parse("node-v20.11.1"), None);
+        assert_eq!(parse("node-v20.11.1-freebsd-x64"), None);
+        assert_eq!(parse("node-v20.11.1-darwin-x64-musl"), None);
+        assert_eq!(parse(".staging-abc"), None);
+    }
+}
@@ -12,6 +12,13 @@ impl Lts {
     pub const fn is_code_name(&self) -> bool {
         matches!(self, Self::CodeName(_))
     }
+
+    pub fn code_name(&self) -> Option<&str> {
+        match self {
+            Self::CodeName(code_name) => Some(code_name),
+            Self::False => None,
+        }
+    }
 }
 
 impl fmt::Display for Lts {
@@ -1,5 +1,9 @@
+pub use channel::Channel;
+pub use installed::InstalledRelease;
 pub use lts::Lts;
 pub use release::Release;
 
+mod channel;
+mod installed;
 mod lts;
 mod release;
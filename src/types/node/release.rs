@@ -1,19 +1,24 @@
 use core::time::Duration;
 
-use std::{fs, io::Read as _, os, path, process, time::Instant};
+use std::{fs, path, time::Instant};
 
-use demand::Spinner;
+use anyhow::Context as _;
 use serde::{Deserialize, Deserializer};
 use ureq::http::StatusCode;
 
 use crate::{
-    constants::{NODE_DISTRIBUTIONS_INDEX_URL, NODE_DISTRIBUTIONS_URL, NODE_GITHUB_URL},
+    constants::{BYTES_PER_MB, NODE_GITHUB_URL, NODE_UNOFFICIAL_DISTRIBUTIONS_URL},
     exts::HyperlinkExt as _,
-    globals::{NUE_PATH, NUE_RELEASES_PATH},
-    types,
+    globals::{NUE_DOWNLOADS_PATH, NUE_PATH, NUE_RELEASES_PATH},
+    types::{archive::ArchiveFormat, platforms::Platform},
+    utils::{
+        self,
+        download::{Download, ResumableReader},
+        http, index, link, signature, spinner,
+    },
 };
 
-use super::Lts;
+use super::{Channel, InstalledRelease, Lts};
 
 const SPINNER_DOWNLOADING_MESSAGE: fn(&Release, Option<&str>) -> String = |release, progress| {
     format!(
@@ -22,8 +27,6 @@ const SPINNER_DOWNLOADING_MESSAGE: fn(&Release, Option<&str>) -> String = |relea
         progress.map_or_else(String::default, |progress| { format!(" ({progress})") })
     )
 };
-const BUFFER_SIZE: usize = 1024 * 1024;
-const BYTES_PER_MB: f64 = 1_048_576.0;
 const SPINNER_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Deserialize, Clone, Debug)]
@@ -32,170 +35,362 @@ pub struct Release {
     pub version: node_semver::Version,
     pub files: Vec<String>,
     pub lts: Lts,
+    pub date: chrono::NaiveDate,
+    #[serde(default, deserialize_with = "deserialise_optional_version")]
+    pub npm: Option<node_semver::Version>,
+    pub v8: Option<String>,
+    pub uv: Option<String>,
+    pub zlib: Option<String>,
+    pub openssl: Option<String>,
+    /// `NODE_MODULE_VERSION`, the ABI version native addons are built against.
+    pub modules: Option<String>,
+    pub security: bool,
+    #[serde(skip)]
+    pub channel: Channel,
 }
 
 impl Release {
-    pub fn install(&self) -> anyhow::Result<()> {
-        if !self.is_supported_by_current_platform() {
-            anyhow::bail!("This release is not supported by the current platform.");
+    /// Downloads and unpacks the release for `platform`. Releases that can
+    /// run here are validated and linked, others are only unpacked.
+    pub fn install(&self, platform: Platform, skip_signature: bool) -> anyhow::Result<()> {
+        if !self.is_supported_by(platform) {
+            anyhow::bail!(
+                "This release is not available for `{}`.",
+                platform.node_platform_string()
+            );
         }
 
-        let mut response = ureq::get(&self.get_download_url()).call()?;
-        if response.status() != StatusCode::OK {
-            anyhow::bail!("Failed to download release: {}", response.status());
-        }
-
-        Spinner::new(SPINNER_DOWNLOADING_MESSAGE(self, None)).run(
-            |spinner| -> anyhow::Result<()> {
-                let content_length = response.headers().get("Content-Length").unwrap().to_str()?.parse::<usize>()?;
-
-                let mut file_chunks = Vec::with_capacity(content_length);
-                let mut buffer = vec![0; BUFFER_SIZE];
-                let mut reader = response.body_mut().as_reader();
+        let (archive_format, expected_checksum) =
+            self.negotiate_archive(platform, skip_signature)?;
+        let archive_file_name = self.get_archive_file_name(platform, archive_format);
 
-                let mut last_update = Instant::now();
+        if !NUE_RELEASES_PATH.try_exists()? {
+            fs::create_dir_all(&*NUE_RELEASES_PATH)?;
+        }
 
-                while let Ok(read_bytes) = reader.read(&mut buffer) {
-                    if read_bytes == 0 {
-                        break;
-                    }
+        if !NUE_DOWNLOADS_PATH.try_exists()? {
+            fs::create_dir_all(&*NUE_DOWNLOADS_PATH)?;
+        }
 
-                    file_chunks.extend_from_slice(&buffer[..read_bytes]);
+        let part_path = NUE_DOWNLOADS_PATH.join(format!("{archive_file_name}.part"));
+        let part_file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&part_path)?;
+        let resumed_bytes = part_file.metadata()?.len();
 
-                    if last_update.elapsed() >= SPINNER_UPDATE_INTERVAL {
-                      spinner.title(SPINNER_DOWNLOADING_MESSAGE(
-                          self,
-                          Some(&format!("{:.2}/{:.2}MiB",
-                              file_chunks.len() as f64 / BYTES_PER_MB,
-                              content_length as f64 / BYTES_PER_MB
-                          )),
-                      ))?;
+        spinner::run(
+            SPINNER_DOWNLOADING_MESSAGE(self, None),
+            |spinner| -> anyhow::Result<()> {
+                let reader = ResumableReader::open(
+                    self.get_download_url(platform, archive_format),
+                    resumed_bytes,
+                )?;
+                let content_length = reader.content_length();
 
-                      last_update = Instant::now();
-                    }
+                let download_start = Instant::now();
+                let mut last_update = Instant::now();
+                let download =
+                    Download::new(reader, part_file, resumed_bytes, |downloaded_bytes| {
+                        if last_update.elapsed() >= SPINNER_UPDATE_INTERVAL {
+                            let progress = content_length.map_or_else(
+                                || {
+                                    format!(
+                                        "{:.2}MiB, {:.2}MiB/s",
+                                        downloaded_bytes as f64 / BYTES_PER_MB,
+                                        downloaded_bytes.saturating_sub(resumed_bytes) as f64
+                                            / BYTES_PER_MB
+                                            / download_start.elapsed().as_secs_f64()
+                                    )
+                                },
+                                |content_length| {
+                                    format!(
+                                        "{:.2}/{:.2}MiB",
+                                        downloaded_bytes as f64 / BYTES_PER_MB,
+                                        content_length as f64 / BYTES_PER_MB
+                                    )
+                                },
+                            );
+
+                            spinner.title(SPINNER_DOWNLOADING_MESSAGE(self, Some(&progress)))?;
+                            last_update = Instant::now();
+                        }
+
+                        Ok(())
+                    });
+
+                let (archive_file, checksum) = download.finish().with_context(|| {
+                    format!(
+                        "Failed to download release. The partial download is kept at `{}` to resume later, run `nue clean` to discard it.",
+                        part_path.display()
+                    )
+                })?;
+
+                // The archive is only unpacked once the whole download matches
+                // its checksum, so tampered archives are never unpacked.
+                spinner.title("Verifying checksum...")?;
+                if checksum != expected_checksum {
+                    fs::remove_file(&part_path)?;
+                    anyhow::bail!(
+                        "Checksum mismatch for `{archive_file_name}`: expected {expected_checksum}, got {checksum}. The download might be corrupted or tampered with."
+                    );
                 }
 
-                spinner.title("Unpacking archive...")?;
-                extract_node_archive(file_chunks.as_slice())?;
-
-                spinner.title("Linking node folder...")?;
-                #[cfg(unix)]
-                os::unix::fs::symlink(
-                    NUE_RELEASES_PATH.join(self.get_archive_string()),
-                    NUE_PATH.join("node"),
+                spinner.title("Unpacking release...")?;
+                let staging_directory = tempfile::Builder::new()
+                    .prefix(".staging-")
+                    .tempdir_in(&*NUE_RELEASES_PATH)?;
+                archive_format.unpack_file(archive_file, staging_directory.path())?;
+                fs::remove_file(&part_path)?;
+
+                spinner.title("Validating release...")?;
+                let installed_release = InstalledRelease {
+                    version: self.version.clone(),
+                    channel: self.channel,
+                    platform,
+                    path: NUE_RELEASES_PATH.join(self.get_release_directory_name(platform)),
+                };
+                installed_release.promote_staged(
+                    staging_directory.path(),
+                    &self.get_archive_string(platform),
+                    platform.is_runnable(),
                 )?;
 
-                #[cfg(windows)]
-                if let Err(error) = os::windows::fs::symlink_dir(
-                    NUE_RELEASES_PATH.join(self.get_archive_string()),
-                    NUE_PATH.join("node"),
-                ) {
-                    if error.raw_os_error() == Some(1314) {
-                        anyhow::bail!(
-                            "Developer mode must be enabled to install nue. For more information: https://learn.microsoft.com/en-us/windows/apps/get-started/enable-your-device-for-development"
-                        );
-                    }
-
-                    anyhow::bail!(error);
+                if platform.is_runnable() {
+                    spinner.title("Linking node folder...")?;
+                    installed_release.link()?;
                 }
 
                 Ok(())
             },
-        )??;
+        )?;
 
         Ok(())
     }
 
-    pub fn install_from_cache(&self, cached_downloads: &[path::PathBuf]) -> anyhow::Result<()> {
-        Spinner::new("Looking for a cached release...").run(|spinner| -> anyhow::Result<()> {
-            for cache in cached_downloads {
-                if cache.try_exists()? && cache.ends_with(self.get_archive_string()) {
-                    spinner.title("Linking cached version...")?;
-
-                    if NUE_PATH.join("node").exists() {
-                        fs::remove_dir_all(NUE_PATH.join("node"))?;
+    pub fn install_from_cache(
+        &self,
+        platform: Platform,
+        cached_downloads: &[path::PathBuf],
+    ) -> anyhow::Result<()> {
+        spinner::run(
+            "Looking for a cached release...",
+            |spinner| -> anyhow::Result<()> {
+                for cache in cached_downloads {
+                    if cache.try_exists()?
+                        && cache.ends_with(self.get_release_directory_name(platform))
+                    {
+                        spinner.title("Linking cached version...")?;
+                        link::link_node_release(cache)?;
+
+                        return Ok(());
                     }
+                }
 
-                    #[cfg(unix)]
-                    os::unix::fs::symlink(cache, NUE_PATH.join("node"))?;
-
-                    #[cfg(windows)]
-                    os::windows::fs::symlink_dir(cache, NUE_PATH.join("node"))?;
+                anyhow::bail!("No cached release found.");
+            },
+        )
+    }
 
-                    return Ok(());
-                }
+    pub fn check_installed(&self, platform: Platform) -> anyhow::Result<bool> {
+        if Platform::current() != Some(platform) {
+            let release_path = NUE_RELEASES_PATH.join(self.get_release_directory_name(platform));
+            if platform.is_runnable() {
+                return Ok(NUE_PATH
+                    .join("node")
+                    .read_link()
+                    .is_ok_and(|linked_path| linked_path == release_path));
             }
 
-            anyhow::bail!("No cached release found.");
-        })?
-    }
+            return Ok(release_path.try_exists()?);
+        }
 
-    pub fn check_installed(&self) -> anyhow::Result<bool> {
         let nue_node_path = NUE_PATH.join("node");
         if !nue_node_path.try_exists()? {
             return Ok(false);
         }
 
-        #[cfg(unix)]
-        let version = process::Command::new(nue_node_path.join("bin").join("node"))
-            .arg("--version")
-            .output()?
-            .stdout;
-        #[cfg(windows)]
-        let version = process::Command::new(nue_node_path.join("node.exe"))
-            .arg("--version")
-            .output()?
-            .stdout;
-
-        if !String::from_utf8_lossy(&version).contains(&self.version.to_string()) {
+        let Ok(version) = utils::check::node_version(&nue_node_path) else {
+            return Ok(false);
+        };
+
+        if version != format!("v{}", self.version) {
             return Ok(false);
         }
 
         Ok(true)
     }
 
-    pub fn get_all_releases() -> anyhow::Result<Vec<Self>> {
-        let mut response = ureq::get(NODE_DISTRIBUTIONS_INDEX_URL).call()?;
-        if response.status() != StatusCode::OK {
-            anyhow::bail!("Failed to fetch releases: {}", response.status());
+    /// Finds the newest release in the same major line that is newer than
+    /// `version`. LTS code names are given to a whole major line, so this also
+    /// stays within the LTS line of `version`.
+    pub fn find_upgrade<'a>(
+        releases: &'a [Self],
+        version: &node_semver::Version,
+    ) -> Option<&'a Self> {
+        releases
+            .iter()
+            .filter(|release| release.version.major == version.major && release.version > *version)
+            .max_by_key(|release| &release.version)
+    }
+
+    /// Finds the newest security release in the same major line that is newer
+    /// than `version`, meaning `version` is missing security fixes.
+    pub fn find_security_update<'a>(
+        releases: &'a [Self],
+        version: &node_semver::Version,
+    ) -> Option<&'a Self> {
+        releases
+            .iter()
+            .filter(|release| {
+                release.security
+                    && release.version.major == version.major
+                    && release.version > *version
+            })
+            .max_by_key(|release| &release.version)
+    }
+
+    pub fn get_all_releases(channel: Channel, platform: Platform) -> anyhow::Result<Vec<Self>> {
+        let mut releases: Vec<Self> =
+            serde_json::from_str(&index::fetch_index(channel, platform)?)?;
+        for release in &mut releases {
+            release.channel = channel;
         }
 
-        let releases = response.body_mut().read_json()?;
         Ok(releases)
     }
 
-    pub fn get_download_url(&self) -> String {
+    pub fn get_cached_releases(channel: Channel, platform: Platform) -> Option<Vec<Self>> {
+        let mut releases: Vec<Self> =
+            serde_json::from_str(&index::cached_index(channel, platform)?).ok()?;
+        for release in &mut releases {
+            release.channel = channel;
+        }
+
+        Some(releases)
+    }
+
+    /// Picks the most preferred archive format that is both listed for
+    /// `platform` and published in `SHASUMS256.txt`, returning it with its
+    /// expected checksum.
+    pub fn negotiate_archive(
+        &self,
+        platform: Platform,
+        skip_signature: bool,
+    ) -> anyhow::Result<(ArchiveFormat, String)> {
+        let mut response = http::get(&self.get_shasums_url(platform))?.call()?;
+        if response.status() != StatusCode::OK {
+            anyhow::bail!("Failed to fetch checksums: {}", response.status());
+        }
+
+        let shasums = response.body_mut().read_to_vec()?;
+
+        // Only the default unofficial builds are known to ship unsigned
+        // checksums, custom musl mirrors are verified like any other.
+        if self
+            .get_shasums_url(platform)
+            .starts_with(NODE_UNOFFICIAL_DISTRIBUTIONS_URL)
+            && !skip_signature
+        {
+            eprintln!(
+                "Unofficial musl builds don't publish signed checksums, only verifying the checksum."
+            );
+        } else if !self.channel.is_signed() && !skip_signature {
+            eprintln!(
+                "{} builds don't publish signed checksums, only verifying the checksum.",
+                self.channel
+            );
+        } else if !skip_signature {
+            let mut response = http::get(&self.get_shasums_signature_url(platform))?.call()?;
+            if response.status() != StatusCode::OK {
+                anyhow::bail!("Failed to fetch checksums signature: {}", response.status());
+            }
+
+            signature::verify_detached_signature(
+                &shasums,
+                &response.body_mut().read_to_vec()?,
+                &signature::release_keys()?,
+            )
+            .map_err(|error| anyhow::anyhow!("Failed to verify SHASUMS256.txt: {error}"))?;
+        }
+
+        let shasums = String::from_utf8(shasums)?;
+
+        self.supported_archive_formats(platform)
+            .into_iter()
+            .find_map(|format| {
+                let archive_file_name = self.get_archive_file_name(platform, format);
+                shasums.lines().find_map(|line| {
+                    let (checksum, file_name) = line.split_once(char::is_whitespace)?;
+                    (file_name.trim() == archive_file_name)
+                        .then(|| (format, checksum.to_lowercase()))
+                })
+            })
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No checksum found for any `{}` archive in SHASUMS256.txt.",
+                    self.get_archive_string(platform)
+                )
+            })
+    }
+
+    pub fn get_download_url(&self, platform: Platform, format: ArchiveFormat) -> String {
         format!(
-            "{}/v{}/{}.{}",
-            NODE_DISTRIBUTIONS_URL,
+            "{}/v{}/{}",
+            self.channel.distribution_url(platform),
             self.version,
-            self.get_archive_string(),
-            types::platforms::Platform::current()
-                .expect("unsupported platform")
-                .node_archive_extension()
+            self.get_archive_file_name(platform, format)
         )
     }
 
+    pub fn get_shasums_url(&self, platform: Platform) -> String {
+        format!(
+            "{}/v{}/SHASUMS256.txt",
+            self.channel.distribution_url(platform),
+            self.version
+        )
+    }
+
+    pub fn get_shasums_signature_url(&self, platform: Platform) -> String {
+        format!("{}.sig", self.get_shasums_url(platform))
+    }
+
     pub fn get_github_release_url(&self) -> String {
         format!("{}/releases/tag/v{}", NODE_GITHUB_URL, self.version)
     }
 
-    pub fn get_archive_string(&self) -> String {
+    pub fn get_archive_string(&self, platform: Platform) -> String {
+        format!("node-v{}-{}", self.version, platform.node_platform_string())
+    }
+
+    pub fn get_release_directory_name(&self, platform: Platform) -> String {
+        self.channel.release_directory_name(&self.version, platform)
+    }
+
+    pub fn get_archive_file_name(&self, platform: Platform, format: ArchiveFormat) -> String {
         format!(
-            "node-v{}-{}",
-            self.version,
-            types::platforms::Platform::current()
-                .expect("unsupported platform")
-                .node_platform_string()
+            "{}.{}",
+            self.get_archive_string(platform),
+            format.extension()
         )
     }
 
+    pub fn supported_archive_formats(&self, platform: Platform) -> Vec<ArchiveFormat> {
+        platform
+            .node_archive_formats()
+            .into_iter()
+            .filter(|(files_entry, _)| self.files.contains(files_entry))
+            .map(|(_, format)| format)
+            .collect()
+    }
+
+    pub fn is_supported_by(&self, platform: Platform) -> bool {
+        !self.supported_archive_formats(platform).is_empty()
+    }
+
     pub fn is_supported_by_current_platform(&self) -> bool {
-        self.files.contains(
-            &types::platforms::Platform::current()
-                .expect("unsupported platform")
-                .node_index_platform_string(),
-        )
+        Platform::current().is_some_and(|platform| self.is_supported_by(platform))
     }
 }
 
@@ -210,27 +405,14 @@ where
         .map_err(serde::de::Error::custom)
 }
 
-fn extract_node_archive(file_chunks: &[u8]) -> Result<(), anyhow::Error> {
-    if !NUE_RELEASES_PATH.try_exists()? {
-        fs::create_dir_all(&*NUE_RELEASES_PATH)?;
-    }
-
-    #[cfg(unix)]
-    {
-        use binstall_tar::Archive;
-        use liblzma::decode_all;
-
-        let decoded = decode_all(file_chunks)?;
-        Archive::new(decoded.as_slice()).unpack(&*NUE_RELEASES_PATH)?;
-    }
-
-    #[cfg(windows)]
-    {
-        use sevenz_rust2::decompress;
-        use std::io;
-
-        decompress(io::Cursor::new(file_chunks), &*NUE_RELEASES_PATH)?;
-    }
-
-    Ok(())
+fn deserialise_optional_version<'de, D>(
+    deserializer: D,
+) -> Result<Option<node_semver::Version>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let version: Option<String> = Deserialize::deserialize(deserializer)?;
+    version
+        .map(|version| version.parse().map_err(serde::de::Error::custom))
+        .transpose()
 }
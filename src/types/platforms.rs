@@ -1,6 +1,15 @@
+use std::fs;
+
+use clap::ValueEnum;
+use serde::Deserialize;
+
+use crate::{globals::LIBC, types::config::Config};
+
+use super::archive::ArchiveFormat;
+
 macro_rules! impl_arch_and_traits {
     ($type:ident, $($variant:ident => ($std_arch:expr, $node_arch:expr)),+ $(,)?) => {
-        #[derive(Debug)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         pub enum $type {
             $($variant,)+
         }
@@ -13,6 +22,13 @@ macro_rules! impl_arch_and_traits {
                 }
             }
 
+            pub fn from_node_arch(node_arch: &str) -> Option<Self> {
+                match node_arch {
+                    $($node_arch => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+
             pub const fn node_arch(&self) -> &'static str {
                 match self {
                     $(Self::$variant => $node_arch,)+
@@ -30,6 +46,11 @@ impl_arch_and_traits!(LinuxArch,
     X64 => ("x86_64", "x64")
 );
 
+impl_arch_and_traits!(LinuxMuslArch,
+    ARM64 => ("aarch64", "arm64"),
+    X64 => ("x86_64", "x64")
+);
+
 impl_arch_and_traits!(MacArch,
     ARM64 => ("aarch64", "arm64"),
     X64 => ("x86_64", "x64")
@@ -41,9 +62,48 @@ impl_arch_and_traits!(WindowsArch,
     X64 => ("x86_64", "x64")
 );
 
-#[derive(Debug)]
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Libc {
+    Glibc,
+    Musl,
+}
+
+impl Libc {
+    /// The libc to install releases for: `--libc`, then the `libc` config
+    /// key, then whatever the system uses.
+    pub fn current() -> Self {
+        *LIBC.get_or_init(|| Config::get().libc.unwrap_or_else(Self::detect))
+    }
+
+    /// Checks for the system's musl loader rather than how nue was built, as
+    /// a static musl build of nue can run on glibc systems too.
+    fn detect() -> Self {
+        if !cfg!(target_os = "linux") {
+            return Self::Glibc;
+        }
+
+        let has_musl_loader = fs::read_dir("/lib").is_ok_and(|entries| {
+            entries.flatten().any(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .is_some_and(|name| name.starts_with("ld-musl-"))
+            })
+        });
+
+        if has_musl_loader {
+            Self::Musl
+        } else {
+            Self::Glibc
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Platform {
     Linux(LinuxArch),
+    LinuxMusl(LinuxMuslArch),
     Mac(MacArch),
     Windows(WindowsArch),
 }
@@ -51,33 +111,150 @@ pub enum Platform {
 impl Platform {
     pub fn current() -> Option<Self> {
         match std::env::consts::OS {
-            "linux" => Some(Self::Linux(LinuxArch::current()?)),
+            "linux" => match Libc::current() {
+                Libc::Glibc => Some(Self::Linux(LinuxArch::current()?)),
+                Libc::Musl => Some(Self::LinuxMusl(LinuxMuslArch::current()?)),
+            },
             "macos" => Some(Self::Mac(MacArch::current()?)),
             "windows" => Some(Self::Windows(WindowsArch::current()?)),
             _ => None,
         }
     }
 
-    pub const fn node_archive_extension(&self) -> &'static str {
+    pub fn require_current() -> anyhow::Result<Self> {
+        Self::current().ok_or_else(|| anyhow::anyhow!("The current platform isn't supported."))
+    }
+
+    /// Builds a platform from Node's OS and architecture names, defaulting
+    /// to the current platform for any part that isn't given.
+    pub fn from_node_names(os: Option<&str>, arch: Option<&str>) -> anyhow::Result<Self> {
+        let current = Self::current();
+        let os = os
+            .map(str::to_lowercase)
+            .or_else(|| current.map(|platform| platform.node_os().to_string()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("The current platform isn't supported, pass `--platform`.")
+            })?;
+        let arch = arch
+            .map(str::to_lowercase)
+            .or_else(|| current.map(|platform| platform.node_arch().to_string()))
+            .ok_or_else(|| {
+                anyhow::anyhow!("The current architecture isn't supported, pass `--arch`.")
+            })?;
+
+        let platform = match os.as_str() {
+            "linux" => match Libc::current() {
+                Libc::Glibc => LinuxArch::from_node_arch(&arch).map(Self::Linux),
+                Libc::Musl => LinuxMuslArch::from_node_arch(&arch).map(Self::LinuxMusl),
+            },
+            "darwin" | "mac" | "macos" | "osx" => MacArch::from_node_arch(&arch).map(Self::Mac),
+            "win" | "windows" => WindowsArch::from_node_arch(&arch).map(Self::Windows),
+            _ => anyhow::bail!("Unknown platform `{os}`, expected `linux`, `darwin` or `win`."),
+        };
+
+        platform.ok_or_else(|| anyhow::anyhow!("Node isn't built for `{arch}` on `{os}`."))
+    }
+
+    /// Whether releases for this platform can run here, either natively or
+    /// through Rosetta or Windows' x86/x64 emulation.
+    pub fn is_runnable(&self) -> bool {
+        let Some(current) = Self::current() else {
+            return false;
+        };
+
+        *self == current
+            || matches!(
+                (self, current),
+                (Self::Mac(MacArch::X64), Self::Mac(MacArch::ARM64))
+                    | (
+                        Self::Windows(WindowsArch::X86 | WindowsArch::X64),
+                        Self::Windows(WindowsArch::ARM64)
+                    )
+                    | (
+                        Self::Windows(WindowsArch::X86),
+                        Self::Windows(WindowsArch::X64)
+                    )
+            )
+    }
+
+    pub const fn node_os(&self) -> &'static str {
+        match self {
+            Self::Linux(_) | Self::LinuxMusl(_) => "linux",
+            Self::Mac(_) => "darwin",
+            Self::Windows(_) => "win",
+        }
+    }
+
+    pub const fn node_arch(&self) -> &'static str {
         match self {
-            Self::Linux(_) | Self::Mac(_) => "tar.xz",
-            Self::Windows(_) => "7z",
+            Self::Linux(arch) => arch.node_arch(),
+            Self::LinuxMusl(arch) => arch.node_arch(),
+            Self::Mac(arch) => arch.node_arch(),
+            Self::Windows(arch) => arch.node_arch(),
         }
     }
 
     pub fn node_platform_string(&self) -> String {
         match self {
             Self::Linux(arch) => format!("linux-{}", arch.node_arch()),
+            Self::LinuxMusl(arch) => format!("linux-{}-musl", arch.node_arch()),
             Self::Mac(arch) => format!("darwin-{}", arch.node_arch()),
             Self::Windows(arch) => format!("win-{}", arch.node_arch()),
         }
     }
 
-    pub fn node_index_platform_string(&self) -> String {
+    /// Splits a release name like `node-v20.11.1-linux-x64-musl` into the part
+    /// before the platform and the platform it was built for.
+    pub fn split_release_name(name: &str) -> Option<(&str, Self)> {
+        let (name, musl) = name
+            .strip_suffix("-musl")
+            .map_or((name, false), |name| (name, true));
+        let (name, arch) = name.rsplit_once('-')?;
+        let (name, os) = name.rsplit_once('-')?;
+
+        let platform = match (os, musl) {
+            ("linux", false) => LinuxArch::from_node_arch(arch).map(Self::Linux),
+            ("linux", true) => LinuxMuslArch::from_node_arch(arch).map(Self::LinuxMusl),
+            ("darwin", false) => MacArch::from_node_arch(arch).map(Self::Mac),
+            ("win", false) => WindowsArch::from_node_arch(arch).map(Self::Windows),
+            _ => None,
+        }?;
+
+        Some((name, platform))
+    }
+
+    /// Archive formats this platform can be installed from, most preferred
+    /// first, along with the `files` entry of the release index publishing them.
+    pub fn node_archive_formats(&self) -> [(String, ArchiveFormat); 2] {
         match self {
-            Self::Linux(arch) => format!("linux-{}", arch.node_arch()),
-            Self::Mac(arch) => format!("osx-{}-tar", arch.node_arch()),
-            Self::Windows(arch) => format!("win-{}-7z", arch.node_arch()),
+            Self::Linux(arch) => {
+                let files_entry = format!("linux-{}", arch.node_arch());
+                [
+                    (files_entry.clone(), ArchiveFormat::TarXz),
+                    (files_entry, ArchiveFormat::TarGz),
+                ]
+            }
+            Self::LinuxMusl(arch) => {
+                let files_entry = format!("linux-{}-musl", arch.node_arch());
+                [
+                    (files_entry.clone(), ArchiveFormat::TarXz),
+                    (files_entry, ArchiveFormat::TarGz),
+                ]
+            }
+            Self::Mac(arch) => {
+                let files_entry = format!("osx-{}-tar", arch.node_arch());
+                [
+                    (files_entry.clone(), ArchiveFormat::TarXz),
+                    (files_entry, ArchiveFormat::TarGz),
+                ]
+            }
+            Self::Windows(arch) => [
+                (
+                    format!("win-{}-7z", arch.node_arch()),
+                    ArchiveFormat::SevenZip,
+                ),
+                (format!("win-{}-zip", arch.node_arch()), ArchiveFormat::Zip),
+            ],
         }
     }
 }
@@ -0,0 +1,88 @@
+use std::{fs, io};
+
+use crate::{
+    globals::NUE_ALIASES_PATH,
+    types::{
+        node::{Channel, InstalledRelease, Release},
+        platforms::Platform,
+    },
+};
+
+/// Alias names are stored as file names, so they are limited to lowercase
+/// letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|character| {
+            character.is_ascii_lowercase()
+                || character.is_ascii_digit()
+                || matches!(character, '-' | '_')
+        })
+}
+
+pub fn read_alias(name: &str) -> anyhow::Result<Option<String>> {
+    if !is_valid_name(name) {
+        return Ok(None);
+    }
+
+    match fs::read_to_string(NUE_ALIASES_PATH.join(name)) {
+        Ok(target) => Ok(Some(target.trim().to_string())),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+pub fn write_alias(name: &str, target: &str) -> anyhow::Result<()> {
+    fs::create_dir_all(&*NUE_ALIASES_PATH)?;
+    fs::write(NUE_ALIASES_PATH.join(name), format!("{target}\n"))?;
+
+    Ok(())
+}
+
+pub fn remove_alias(name: &str) -> anyhow::Result<bool> {
+    if !is_valid_name(name) {
+        return Ok(false);
+    }
+
+    match fs::remove_file(NUE_ALIASES_PATH.join(name)) {
+        Ok(()) => Ok(true),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(false),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// LTS code names known without going online, from the cached release index
+/// and installed releases.
+pub fn local_lts_code_names() -> anyhow::Result<Vec<String>> {
+    let mut code_names: Vec<String> = Platform::current()
+        .and_then(|platform| Release::get_cached_releases(Channel::Release, platform))
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|release| release.lts.code_name().map(str::to_lowercase))
+        .collect();
+
+    code_names.extend(
+        InstalledRelease::get_all_installed()?
+            .iter()
+            .filter_map(|release| release.lts().code_name().map(str::to_lowercase)),
+    );
+
+    Ok(code_names)
+}
+
+pub fn get_all_aliases() -> anyhow::Result<Vec<(String, String)>> {
+    let Ok(entries) = fs::read_dir(&*NUE_ALIASES_PATH) else {
+        return Ok(vec![]);
+    };
+
+    let mut aliases = vec![];
+    for entry in entries {
+        let name = entry?.file_name().to_string_lossy().to_string();
+
+        if let Some(target) = read_alias(&name)? {
+            aliases.push((name, target));
+        }
+    }
+
+    aliases.sort();
+    Ok(aliases)
+}
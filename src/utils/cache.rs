@@ -1,6 +1,9 @@
 use std::{fs, path};
 
-use crate::globals::NUE_RELEASES_PATH;
+use crate::{
+    globals::{NUE_DOWNLOADS_PATH, NUE_RELEASES_PATH},
+    utils::check,
+};
 
 pub fn find_cached_node_downloads() -> anyhow::Result<Vec<path::PathBuf>> {
     let mut caches = vec![];
@@ -15,7 +18,12 @@ pub fn find_cached_node_downloads() -> anyhow::Result<Vec<path::PathBuf>> {
                     continue;
                 }
 
-                if entry.file_name().to_string_lossy().starts_with("node-") {
+                // Releases unpacked for another OS keep that OS' layout.
+                if entry.file_name().to_string_lossy().starts_with("node-")
+                    && (check::node_executable_path(&path).is_file()
+                        || path.join("bin").join("node").is_file()
+                        || path.join("node.exe").is_file())
+                {
                     caches.push(path);
                 }
             }
@@ -27,3 +35,41 @@ pub fn find_cached_node_downloads() -> anyhow::Result<Vec<path::PathBuf>> {
 
     Ok(caches)
 }
+
+pub fn find_partial_downloads() -> anyhow::Result<Vec<path::PathBuf>> {
+    let Ok(entries) = fs::read_dir(&*NUE_DOWNLOADS_PATH) else {
+        return Ok(vec![]);
+    };
+
+    let mut partial_downloads = vec![];
+    for entry in entries {
+        let path = entry?.path();
+
+        if path.is_file()
+            && path
+                .extension()
+                .is_some_and(|extension| extension == "part")
+        {
+            partial_downloads.push(path);
+        }
+    }
+
+    Ok(partial_downloads)
+}
+
+pub fn find_staging_directories() -> anyhow::Result<Vec<path::PathBuf>> {
+    let Ok(entries) = fs::read_dir(&*NUE_RELEASES_PATH) else {
+        return Ok(vec![]);
+    };
+
+    let mut staging_directories = vec![];
+    for entry in entries {
+        let entry = entry?;
+
+        if entry.file_name().to_string_lossy().starts_with(".staging-") {
+            staging_directories.push(entry.path());
+        }
+    }
+
+    Ok(staging_directories)
+}
@@ -1,4 +1,4 @@
-use std::env;
+use std::{env, path, process};
 
 pub fn is_node_in_path() -> bool {
     match env::var("PATH") {
@@ -19,3 +19,43 @@ pub fn is_node_in_path() -> bool {
         Err(_) => false,
     }
 }
+
+pub fn node_executable_path(release_path: &path::Path) -> path::PathBuf {
+    #[cfg(unix)]
+    {
+        release_path.join("bin").join("node")
+    }
+    #[cfg(windows)]
+    {
+        release_path.join("node.exe")
+    }
+}
+
+pub fn node_version(release_path: &path::Path) -> anyhow::Result<String> {
+    let output = process::Command::new(node_executable_path(release_path))
+        .arg("--version")
+        .output()?;
+
+    if !output.status.success() {
+        anyhow::bail!("`node --version` exited with {}", output.status);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+pub fn validate_node_release(
+    release_path: &path::Path,
+    expected_version: &node_semver::Version,
+) -> anyhow::Result<()> {
+    if !node_executable_path(release_path).is_file() {
+        anyhow::bail!("The release doesn't contain a Node executable.");
+    }
+
+    let version = node_version(release_path)
+        .map_err(|error| anyhow::anyhow!("Failed to run the unpacked Node: {error}"))?;
+    if version != format!("v{expected_version}") {
+        anyhow::bail!("The unpacked Node reports version {version}, expected v{expected_version}.");
+    }
+
+    Ok(())
+}
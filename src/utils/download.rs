@@ -0,0 +1,298 @@
+use core::time::Duration;
+
+use std::{
+    fs,
+    io::{self, Read, Seek as _, Write as _},
+    thread,
+};
+
+use sha2::{Digest as _, Sha256};
+use ureq::{BodyReader, http::StatusCode};
+
+use super::http;
+
+const MAX_RETRIES: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+pub struct Download<R, F> {
+    reader: R,
+    file: fs::File,
+    resumed_bytes: u64,
+    hasher: Sha256,
+    downloaded_bytes: u64,
+    on_progress: F,
+}
+
+impl<R, F> Download<R, F>
+where
+    R: Read,
+    F: FnMut(u64) -> anyhow::Result<()>,
+{
+    pub fn new(reader: R, file: fs::File, resumed_bytes: u64, on_progress: F) -> Self {
+        Self {
+            reader,
+            file,
+            resumed_bytes,
+            hasher: Sha256::new(),
+            downloaded_bytes: 0,
+            on_progress,
+        }
+    }
+
+    pub fn finish(mut self) -> anyhow::Result<(fs::File, String)> {
+        io::copy(&mut self, &mut io::sink())?;
+
+        self.file.flush()?;
+        self.file.rewind()?;
+
+        Ok((self.file, format!("{:x}", self.hasher.finalize())))
+    }
+}
+
+impl<R, F> Read for Download<R, F>
+where
+    R: Read,
+    F: FnMut(u64) -> anyhow::Result<()>,
+{
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read_bytes = if self.resumed_bytes > 0 {
+            let limit = buffer
+                .len()
+                .min(usize::try_from(self.resumed_bytes).unwrap_or(usize::MAX));
+
+            let read_bytes = self.file.read(&mut buffer[..limit])?;
+            if read_bytes == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "partial download is shorter than expected",
+                ));
+            }
+
+            self.resumed_bytes -= read_bytes as u64;
+            read_bytes
+        } else {
+            let read_bytes = self.reader.read(buffer)?;
+            self.file.write_all(&buffer[..read_bytes])?;
+            read_bytes
+        };
+
+        self.hasher.update(&buffer[..read_bytes]);
+        self.downloaded_bytes += read_bytes as u64;
+
+        (self.on_progress)(self.downloaded_bytes).map_err(io::Error::other)?;
+
+        Ok(read_bytes)
+    }
+}
+
+pub struct ResumableReader {
+    url: String,
+    offset: u64,
+    reader: Option<BodyReader<'static>>,
+    content_length: Option<u64>,
+}
+
+impl ResumableReader {
+    pub fn open(url: String, offset: u64) -> anyhow::Result<Self> {
+        let mut reader = Self {
+            url,
+            offset,
+            reader: None,
+            content_length: None,
+        };
+        reader.connect_with_retries()?;
+
+        Ok(reader)
+    }
+
+    pub const fn content_length(&self) -> Option<u64> {
+        self.content_length
+    }
+
+    fn connect(&mut self) -> anyhow::Result<()> {
+        self.reader = None;
+
+        let mut request = http::get(&self.url)?;
+        if self.offset > 0 {
+            request = request.header("Range", format!("bytes={}-", self.offset));
+        }
+
+        let response = match request.call() {
+            Ok(response) => response,
+            Err(ureq::Error::StatusCode(416)) if self.offset > 0 => {
+                self.content_length = Some(self.offset);
+                return Ok(());
+            }
+            Err(error) => return Err(error.into()),
+        };
+
+        let header = |name| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+
+        match response.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                self.content_length = header("Content-Range")
+                    .and_then(|range| range.rsplit('/').next()?.parse().ok());
+                self.reader = Some(response.into_body().into_reader());
+            }
+            StatusCode::OK => {
+                self.content_length =
+                    header("Content-Length").and_then(|length| length.parse().ok());
+
+                let mut reader = response.into_body().into_reader();
+                if self.offset > 0 {
+                    let skipped_bytes =
+                        io::copy(&mut (&mut reader).take(self.offset), &mut io::sink())?;
+                    if skipped_bytes != self.offset {
+                        anyhow::bail!("Download is shorter than the partial download.");
+                    }
+                }
+
+                self.reader = Some(reader);
+            }
+            status => anyhow::bail!("Failed to download release: {status}"),
+        }
+
+        Ok(())
+    }
+
+    fn connect_with_retries(&mut self) -> anyhow::Result<()> {
+        let mut attempt = 0;
+
+        loop {
+            match self.connect() {
+                Ok(()) => return Ok(()),
+                Err(error) if attempt < MAX_RETRIES && is_transient(&error) => {
+                    attempt += 1;
+                    thread::sleep(backoff(attempt));
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+impl Read for ResumableReader {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let mut attempt = 0;
+
+        loop {
+            let Some(reader) = &mut self.reader else {
+                return Ok(0);
+            };
+
+            match reader.read(buffer) {
+                Ok(read_bytes) => {
+                    self.offset += read_bytes as u64;
+                    return Ok(read_bytes);
+                }
+                Err(error) if attempt < MAX_RETRIES => {
+                    attempt += 1;
+                    thread::sleep(backoff(attempt));
+
+                    self.connect_with_retries().map_err(|connect_error| {
+                        io::Error::other(format!(
+                            "{error}, and resuming the download failed: {connect_error}"
+                        ))
+                    })?;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+}
+
+fn backoff(attempt: u32) -> Duration {
+    INITIAL_BACKOFF * 2_u32.pow(attempt.saturating_sub(1))
+}
+
+/// Only network failures, timeouts, rate limiting and server errors are worth
+/// retrying, anything else fails the same way again.
+fn is_transient(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<ureq::Error>() {
+        Some(ureq::Error::StatusCode(code)) => *code == 429 || *code >= 500,
+        Some(
+            ureq::Error::Io(_)
+            | ureq::Error::Timeout(_)
+            | ureq::Error::HostNotFound
+            | ureq::Error::ConnectionFailed,
+        ) => true,
+        Some(_) => false,
+        None => error.is::<io::Error>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs,
+        io::{self, Read as _, Write as _},
+    };
+
+    use super::Download;
+
+    const HELLO_WORLD_SHA256: &str =
+        "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+    fn part_file(contents: &[u8]) -> (tempfile::NamedTempFile, fs::File) {
+        let mut part = tempfile::NamedTempFile::new().unwrap();
+        part.write_all(contents).unwrap();
+
+        let file = fs::OpenOptions::new()
+            .read(true)
+            .append(true)
+            .open(part.path())
+            .unwrap();
+
+        (part, file)
+    }
+
+    #[test]
+    fn checksum_covers_resumed_and_new_bytes() {
+        let (part, file) = part_file(b"hello ");
+
+        let mut progress = vec![];
+        let download = Download::new(io::Cursor::new(b"world"), file, 6, |downloaded_bytes| {
+            progress.push(downloaded_bytes);
+            Ok(())
+        });
+        let (mut file, checksum) = download.finish().unwrap();
+
+        assert_eq!(checksum, HELLO_WORLD_SHA256);
+        assert_eq!(progress.last(), Some(&11));
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "hello world");
+        assert_eq!(fs::read(part.path()).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn checksum_without_resumed_bytes() {
+        let (part, file) = part_file(b"");
+
+        let download = Download::new(io::Cursor::new(b"hello world"), file, 0, |_| Ok(()));
+        let (_, checksum) = download.finish().unwrap();
+
+        assert_eq!(checksum, HELLO_WORLD_SHA256);
+        assert_eq!(fs::read(part.path()).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn short_partial_download_errors() {
+        let (_part, file) = part_file(b"hel");
+
+        let download = Download::new(io::Cursor::new(b"world"), file, 6, |_| Ok(()));
+        let error = download.finish().unwrap_err();
+
+        assert_eq!(
+            error.downcast_ref::<io::Error>().map(io::Error::kind),
+            Some(io::ErrorKind::UnexpectedEof)
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use core::sync::atomic::Ordering;
+
+use std::{env, fs, sync::OnceLock};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use ureq::{
+    Agent, Proxy, RequestBuilder,
+    http::Uri,
+    tls::{self, Certificate, PemItem, RootCerts, TlsConfig},
+    typestate::WithoutBody,
+};
+
+use crate::{globals::OFFLINE, types::config::Config};
+
+static AGENTS: OnceLock<Agents> = OnceLock::new();
+
+struct Agents {
+    proxied: Agent,
+    direct: Agent,
+    authorization: Option<String>,
+}
+
+impl Agents {
+    fn new() -> anyhow::Result<Self> {
+        let config = Config::get();
+
+        let tls_config = match &config.ca_file {
+            Some(ca_file) => {
+                let certificates = tls::parse_pem(&fs::read(ca_file)?)
+                    .filter_map(|item| match item {
+                        Ok(PemItem::Certificate(certificate)) => Some(Ok(certificate)),
+                        Ok(_) => None,
+                        Err(error) => Some(Err(error)),
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if certificates.is_empty() {
+                    anyhow::bail!("No certificates found in `{}`.", ca_file.display());
+                }
+
+                // Extra roots are trusted alongside the bundled ones, so public
+                // hosts keep working when only the mirror needs a private CA.
+                let root_certs = webpki_root_certs::TLS_SERVER_ROOT_CERTS
+                    .iter()
+                    .map(|certificate| Certificate::from_der(certificate.as_ref()))
+                    .chain(certificates);
+
+                TlsConfig::builder()
+                    .root_certs(RootCerts::from(root_certs))
+                    .build()
+            }
+            None => TlsConfig::default(),
+        };
+
+        let proxy = match &config.proxy {
+            Some(proxy) => Some(Proxy::new(proxy)?),
+            None => Proxy::try_from_env(),
+        };
+
+        let authorization = match (&config.auth_token, &config.auth_username) {
+            (Some(token), _) => Some(format!("Bearer {token}")),
+            (None, Some(username)) => Some(format!(
+                "Basic {}",
+                BASE64.encode(format!(
+                    "{username}:{}",
+                    config.auth_password.as_deref().unwrap_or_default()
+                ))
+            )),
+            (None, None) => None,
+        };
+
+        Ok(Self {
+            proxied: build_agent(proxy, tls_config.clone()),
+            direct: build_agent(None, tls_config),
+            authorization,
+        })
+    }
+}
+
+pub fn get(url: &str) -> anyhow::Result<RequestBuilder<WithoutBody>> {
+    if OFFLINE.load(Ordering::Relaxed) {
+        anyhow::bail!("Network access is disabled in offline mode, can't fetch `{url}`.");
+    }
+
+    let agents = match AGENTS.get() {
+        Some(agents) => agents,
+        None => {
+            let agents = Agents::new()?;
+            AGENTS.get_or_init(|| agents)
+        }
+    };
+
+    let host = url.parse::<Uri>()?.host().unwrap_or_default().to_string();
+    let agent = if is_no_proxy_host(&host) {
+        &agents.direct
+    } else {
+        &agents.proxied
+    };
+
+    let mut request = agent.get(url);
+    if let Some(authorization) = &agents.authorization {
+        if Config::get().is_configured_url(url) {
+            request = request.header("Authorization", authorization);
+        }
+    }
+
+    Ok(request)
+}
+
+fn build_agent(proxy: Option<Proxy>, tls_config: TlsConfig) -> Agent {
+    Agent::config_builder()
+        .proxy(proxy)
+        .tls_config(tls_config)
+        .build()
+        .new_agent()
+}
+
+fn is_no_proxy_host(host: &str) -> bool {
+    let Some(no_proxy) = ["NO_PROXY", "no_proxy"]
+        .into_iter()
+        .find_map(|name| env::var(name).ok())
+    else {
+        return false;
+    };
+
+    no_proxy
+        .split(',')
+        .map(|entry| entry.trim().trim_start_matches('.'))
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            let entry = entry.split(':').next().unwrap_or(entry);
+            entry == "*" || host == entry || host.ends_with(&format!(".{entry}"))
+        })
+}
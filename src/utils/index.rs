@@ -0,0 +1,156 @@
+use core::sync::atomic::Ordering;
+
+use std::{
+    fs,
+    io::Write as _,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize, de::IgnoredAny};
+use ureq::http::StatusCode;
+
+use crate::{
+    globals::{NUE_CACHE_PATH, OFFLINE},
+    types::{config::Config, node::Channel, platforms::Platform},
+};
+
+use super::http;
+
+#[derive(Serialize, Deserialize, Debug)]
+struct IndexMetadata {
+    url: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: u64,
+}
+
+pub fn fetch_index(channel: Channel, platform: Platform) -> anyhow::Result<String> {
+    let index_url = channel.index_url(platform);
+    let cached_index = read_cached_index(channel, platform);
+
+    if OFFLINE.load(Ordering::Relaxed) {
+        return cached_index.map(|(_, index)| index).ok_or_else(|| {
+            anyhow::anyhow!(
+                "No cached release index is available in offline mode. Run nue without `--offline` once to cache it."
+            )
+        });
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    if let Some((metadata, index)) = &cached_index {
+        if now.saturating_sub(metadata.fetched_at) < Config::get().index_ttl {
+            return Ok(index.clone());
+        }
+    }
+
+    let mut request = http::get(&index_url)?;
+    if let Some((metadata, _)) = &cached_index {
+        if let Some(etag) = &metadata.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(last_modified) = &metadata.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+    }
+
+    let mut response = match request.call() {
+        Ok(response) => response,
+        Err(error) => {
+            let Some((metadata, index)) = cached_index else {
+                return Err(error.into());
+            };
+
+            eprintln!(
+                "Failed to fetch releases ({error}), using the cached release index from {} minutes ago.",
+                now.saturating_sub(metadata.fetched_at) / 60
+            );
+            return Ok(index);
+        }
+    };
+
+    let (mut metadata, index) = match (response.status(), cached_index) {
+        (StatusCode::NOT_MODIFIED, Some(cached_index)) => cached_index,
+        (StatusCode::OK, _) => {
+            let header = |name| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string)
+            };
+
+            let metadata = IndexMetadata {
+                url: index_url,
+                etag: header("ETag"),
+                last_modified: header("Last-Modified"),
+                fetched_at: now,
+            };
+
+            (metadata, response.body_mut().read_to_string()?)
+        }
+        (status, _) => anyhow::bail!("Failed to fetch releases: {status}"),
+    };
+
+    metadata.fetched_at = now;
+
+    fs::create_dir_all(&*NUE_CACHE_PATH)?;
+    let (index_file_name, metadata_file_name) = index_file_names(channel, platform);
+    write_cache_file(&index_file_name, index.as_bytes())?;
+    write_cache_file(
+        &metadata_file_name,
+        serde_json::to_string(&metadata)?.as_bytes(),
+    )?;
+
+    Ok(index)
+}
+
+pub fn is_index_cached(channel: Channel, platform: Platform) -> bool {
+    read_cached_index(channel, platform).is_some()
+}
+
+/// The cached index regardless of its age, without going online.
+pub fn cached_index(channel: Channel, platform: Platform) -> Option<String> {
+    read_cached_index(channel, platform).map(|(_, index)| index)
+}
+
+fn read_cached_index(channel: Channel, platform: Platform) -> Option<(IndexMetadata, String)> {
+    let (index_file_name, metadata_file_name) = index_file_names(channel, platform);
+    let metadata: IndexMetadata =
+        serde_json::from_str(&fs::read_to_string(NUE_CACHE_PATH.join(metadata_file_name)).ok()?)
+            .ok()?;
+
+    if metadata.url != channel.index_url(platform) {
+        return None;
+    }
+
+    // A cache left truncated by an older nue is treated like a missing one.
+    let index = fs::read_to_string(NUE_CACHE_PATH.join(index_file_name)).ok()?;
+    serde_json::from_str::<IgnoredAny>(&index).ok()?;
+
+    Some((metadata, index))
+}
+
+/// Writes through a temporary file renamed into place, so concurrent or
+/// interrupted runs never leave a partially written cache behind.
+fn write_cache_file(file_name: &str, contents: &[u8]) -> anyhow::Result<()> {
+    let mut file = tempfile::NamedTempFile::new_in(&*NUE_CACHE_PATH)?;
+    file.write_all(contents)?;
+    file.persist(NUE_CACHE_PATH.join(file_name))?;
+
+    Ok(())
+}
+
+/// Musl builds come from another mirror, so their index is cached apart from
+/// the official one.
+fn index_file_names(channel: Channel, platform: Platform) -> (String, String) {
+    let mut name = match channel {
+        Channel::Release => "index".to_string(),
+        _ => format!("index-{channel}"),
+    };
+    if matches!(platform, Platform::LinuxMusl(_)) {
+        name.push_str("-musl");
+    }
+
+    (format!("{name}.json"), format!("{name}.meta.json"))
+}
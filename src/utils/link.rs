@@ -0,0 +1,39 @@
+use std::{fs, os, path, process};
+
+use crate::globals::NUE_PATH;
+
+pub fn link_node_release(release_path: &path::Path) -> anyhow::Result<()> {
+    let nue_node_path = NUE_PATH.join("node");
+    let temporary_link_path = NUE_PATH.join(format!(".node-{}", process::id()));
+
+    if temporary_link_path.symlink_metadata().is_ok() {
+        fs::remove_dir_all(&temporary_link_path)?;
+    }
+
+    #[cfg(unix)]
+    {
+        os::unix::fs::symlink(release_path, &temporary_link_path)?;
+        fs::rename(&temporary_link_path, nue_node_path)?;
+    }
+
+    #[cfg(windows)]
+    {
+        if let Err(error) = os::windows::fs::symlink_dir(release_path, &temporary_link_path) {
+            if error.raw_os_error() == Some(1314) {
+                anyhow::bail!(
+                    "Developer mode must be enabled to install nue. For more information: https://learn.microsoft.com/en-us/windows/apps/get-started/enable-your-device-for-development"
+                );
+            }
+
+            anyhow::bail!(error);
+        }
+
+        // Directory links can't be renamed over each other on Windows.
+        if nue_node_path.symlink_metadata().is_ok() {
+            fs::remove_dir(&nue_node_path)?;
+        }
+        fs::rename(&temporary_link_path, nue_node_path)?;
+    }
+
+    Ok(())
+}
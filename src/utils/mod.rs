@@ -1,2 +1,10 @@
+pub mod alias;
 pub mod cache;
 pub mod check;
+pub mod download;
+pub mod http;
+pub mod index;
+pub mod link;
+pub mod project;
+pub mod signature;
+pub mod spinner;
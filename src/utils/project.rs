@@ -0,0 +1,107 @@
+use std::{env, fs, path};
+
+const VERSION_FILES: [&str; 2] = [".node-version", ".nvmrc"];
+
+pub fn find_node_version() -> anyhow::Result<Option<(String, path::PathBuf)>> {
+    let current_dir = env::current_dir()?;
+
+    for directory in current_dir.ancestors() {
+        for version_file in VERSION_FILES {
+            let path = directory.join(version_file);
+            if !path.is_file() {
+                continue;
+            }
+
+            // Unreadable files are skipped like missing ones, so a broken file
+            // in an unrelated ancestor doesn't break every command.
+            let Ok(contents) = fs::read_to_string(&path) else {
+                continue;
+            };
+
+            if let Some(version) = parse_version_file(&contents) {
+                return Ok(Some((version, path)));
+            }
+        }
+
+        let path = directory.join("package.json");
+        if path.is_file() {
+            if let Some(version) = fs::read_to_string(&path)
+                .ok()
+                .and_then(|contents| parse_package_json(&contents))
+            {
+                return Ok(Some((version, path)));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+fn parse_package_json(contents: &str) -> Option<String> {
+    let package: serde_json::Value = serde_json::from_str(contents).ok()?;
+
+    package
+        .get("engines")
+        .and_then(|engines| engines.get("node"))
+        .and_then(serde_json::Value::as_str)
+        .map(|version| version.trim().to_string())
+}
+
+fn parse_version_file(contents: &str) -> Option<String> {
+    let version = contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())?
+        .to_lowercase();
+
+    Some(match version.as_str() {
+        "node" | "stable" | "current" => "latest".to_string(),
+        "lts/*" => "lts".to_string(),
+        _ => version
+            .strip_prefix("lts/")
+            .map_or_else(|| version.clone(), str::to_string),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_package_json, parse_version_file};
+
+    #[test]
+    fn version_file_takes_first_non_comment_line() {
+        assert_eq!(
+            parse_version_file("# pinned for CI\n\n  v20.11.1  # current LTS\n18\n"),
+            Some("v20.11.1".to_string())
+        );
+    }
+
+    #[test]
+    fn version_file_maps_nvm_aliases() {
+        assert_eq!(parse_version_file("node"), Some("latest".to_string()));
+        assert_eq!(parse_version_file("stable\n"), Some("latest".to_string()));
+        assert_eq!(parse_version_file("current"), Some("latest".to_string()));
+        assert_eq!(parse_version_file("lts/*"), Some("lts".to_string()));
+        assert_eq!(parse_version_file("lts/Iron"), Some("iron".to_string()));
+    }
+
+    #[test]
+    fn version_file_without_version_is_ignored() {
+        assert_eq!(parse_version_file(""), None);
+        assert_eq!(parse_version_file("# nothing here\n   \n"), None);
+    }
+
+    #[test]
+    fn package_json_reads_engines_node() {
+        assert_eq!(
+            parse_package_json(r#"{"engines": {"node": " >=18 <21 "}}"#),
+            Some(">=18 <21".to_string())
+        );
+        assert_eq!(parse_package_json(r#"{"name": "app"}"#), None);
+    }
+
+    #[test]
+    fn malformed_package_json_is_skipped() {
+        assert_eq!(parse_package_json("{\"engines\": "), None);
+        assert_eq!(parse_package_json(r#"{"engines": {"node": 20}}"#), None);
+    }
+}
@@ -0,0 +1,102 @@
+use std::fs;
+
+use pgp::{Deserializable as _, SignedPublicKey, StandaloneSignature};
+
+use crate::globals::NUE_PATH;
+
+const EMBEDDED_RELEASE_KEYS: &str = include_str!("../../resources/release-keys.asc");
+
+pub fn release_keys() -> anyhow::Result<Vec<SignedPublicKey>> {
+    let mut keys = parse_keyring(EMBEDDED_RELEASE_KEYS)?;
+
+    let user_keyring_path = NUE_PATH.join("release-keys.asc");
+    if user_keyring_path.try_exists()? {
+        keys.extend(parse_keyring(&fs::read_to_string(user_keyring_path)?)?);
+    }
+
+    Ok(keys)
+}
+
+pub fn verify_detached_signature(
+    content: &[u8],
+    signature: &[u8],
+    keys: &[SignedPublicKey],
+) -> anyhow::Result<()> {
+    if keys.is_empty() {
+        anyhow::bail!(
+            "No Node release keys are available to verify the signature. Add them to `{}` or use `--skip-signature`.",
+            NUE_PATH.join("release-keys.asc").display()
+        );
+    }
+
+    let signature = StandaloneSignature::from_bytes(signature)?;
+
+    for key in keys {
+        if signature.verify(key, content).is_ok()
+            || key
+                .public_subkeys
+                .iter()
+                .any(|subkey| signature.verify(subkey, content).is_ok())
+        {
+            return Ok(());
+        }
+    }
+
+    anyhow::bail!("Signature does not match any of the Node release keys.");
+}
+
+fn parse_keyring(keyring: &str) -> anyhow::Result<Vec<SignedPublicKey>> {
+    if keyring.trim().is_empty() {
+        return Ok(vec![]);
+    }
+
+    let (keys, _) = SignedPublicKey::from_string_many(keyring)?;
+    Ok(keys.collect::<Result<_, _>>()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EMBEDDED_RELEASE_KEYS, parse_keyring, verify_detached_signature};
+
+    // Throwaway keys generated with gpg, the private keys aren't kept.
+    const TEST_KEYRING: &str = include_str!("../../resources/tests/release-key.asc");
+    const SHASUMS: &[u8] = include_bytes!("../../resources/tests/SHASUMS256.txt");
+    const SHASUMS_SIGNATURE: &[u8] = include_bytes!("../../resources/tests/SHASUMS256.txt.sig");
+    const SHASUMS_UNKNOWN_SIGNATURE: &[u8] =
+        include_bytes!("../../resources/tests/SHASUMS256.txt.unknown.sig");
+
+    #[test]
+    fn embedded_release_keys_are_present() {
+        assert!(!parse_keyring(EMBEDDED_RELEASE_KEYS).unwrap().is_empty());
+    }
+
+    #[test]
+    fn valid_signature_passes() {
+        let keys = parse_keyring(TEST_KEYRING).unwrap();
+
+        verify_detached_signature(SHASUMS, SHASUMS_SIGNATURE, &keys).unwrap();
+    }
+
+    #[test]
+    fn tampered_content_fails() {
+        let keys = parse_keyring(TEST_KEYRING).unwrap();
+        let mut tampered = SHASUMS.to_vec();
+        tampered[0] = if tampered[0] == b'0' { b'1' } else { b'0' };
+
+        assert!(verify_detached_signature(&tampered, SHASUMS_SIGNATURE, &keys).is_err());
+    }
+
+    #[test]
+    fn signature_from_unknown_key_fails() {
+        let keys = parse_keyring(TEST_KEYRING).unwrap();
+
+        assert!(verify_detached_signature(SHASUMS, SHASUMS_UNKNOWN_SIGNATURE, &keys).is_err());
+    }
+
+    #[test]
+    fn empty_keyring_fails() {
+        let keys = parse_keyring("").unwrap();
+
+        assert!(verify_detached_signature(SHASUMS, SHASUMS_SIGNATURE, &keys).is_err());
+    }
+}
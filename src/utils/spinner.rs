@@ -0,0 +1,34 @@
+use core::sync::atomic::Ordering;
+
+use std::io::{self, IsTerminal as _};
+
+use demand::Spinner;
+
+use crate::globals::QUIET;
+
+pub struct Status<'a>(Option<&'a dyn Fn(String) -> anyhow::Result<()>>);
+
+impl Status<'_> {
+    pub fn title(&self, title: impl Into<String>) -> anyhow::Result<()> {
+        self.0.map_or(Ok(()), |set_title| set_title(title.into()))
+    }
+}
+
+pub fn run<T, F>(title: impl Into<String>, func: F) -> anyhow::Result<T>
+where
+    T: Send,
+    F: FnOnce(&Status) -> anyhow::Result<T> + Send,
+{
+    if QUIET.load(Ordering::Relaxed) || !io::stdout().is_terminal() {
+        return func(&Status(None));
+    }
+
+    Spinner::new(title.into()).run(|spinner| {
+        let set_title = |title: String| -> anyhow::Result<()> {
+            spinner.title(title)?;
+            Ok(())
+        };
+
+        func(&Status(Some(&set_title)))
+    })?
+}